@@ -1,8 +1,11 @@
+#![cfg(feature = "std")]
 #![feature(test)]
+#![cfg_attr(feature = "read_buf", feature(core_io_borrowed_buf, read_buf))]
+#![allow(clippy::needless_return)]
 
 extern crate test;
 
-use peekbufread::PeekRead;
+use peekbufread::{PeekRead, SlicePeekRead};
 use rand::prelude::*;
 use std::io::Cursor;
 use test::Bencher;
@@ -54,7 +57,6 @@ fn read_exact_normal_peekread(bench: &mut Bencher) {
     });
 }
 
-#[cfg(feature = "checkpoint")]
 #[bench]
 fn read_exact_with_checkpoint_peekread(bench: &mut Bencher) {
     let data = generate_1m();
@@ -110,7 +112,6 @@ fn read_normal_peekread(bench: &mut Bencher) {
     });
 }
 
-#[cfg(feature = "checkpoint")]
 #[bench]
 fn read_with_checkpoint_peekread(bench: &mut Bencher) {
     let data = generate_1m();
@@ -131,3 +132,405 @@ fn read_with_checkpoint_peekread(bench: &mut Bencher) {
         .unwrap();
     });
 }
+
+#[bench]
+fn read_exact_normal_without_checkpoints_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::without_checkpoints(Cursor::new(data.as_ref()));
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            // Don't optimize me away
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+            test::black_box(result).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn read_normal_without_checkpoints_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::without_checkpoints(Cursor::new(data.as_ref()));
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            // Don't optimize me away
+            let result = read.read(&mut buf);
+            assert!(result.is_ok());
+            assert_eq!(LEN, result.unwrap());
+        }
+    });
+}
+
+#[bench]
+fn read_exact_normal_slicepeekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = SlicePeekRead::new(data.as_ref());
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            // Don't optimize me away
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+            test::black_box(result).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn read_exact_with_checkpoint_slicepeekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = SlicePeekRead::new(data.as_ref());
+        read.checkpoint(|read| -> Result<(), ()> {
+            let mut buf = [0; LEN];
+            for _ in 0..data.len() / 1024 {
+                use std::io::Read;
+                let result = read.read_exact(&mut buf);
+                assert!(result.is_ok());
+                test::black_box(result).unwrap();
+            }
+
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[bench]
+fn read_normal_slicepeekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = SlicePeekRead::new(data.as_ref());
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            // Don't optimize me away
+            let result = read.read(&mut buf);
+            assert!(result.is_ok());
+            assert_eq!(LEN, result.unwrap());
+        }
+    });
+}
+
+#[bench]
+fn read_with_checkpoint_slicepeekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = SlicePeekRead::new(data.as_ref());
+        read.checkpoint(|read| -> Result<(), ()> {
+            let mut buf = [0; LEN];
+            for _ in 0..data.len() / 1024 {
+                use std::io::Read;
+                let result = read.read(&mut buf);
+                assert!(result.is_ok());
+                assert_eq!(LEN, result.unwrap());
+            }
+
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+/// Peeks 16 bytes of every 1 KiB record and skips the record
+#[bench]
+fn peek_slice_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::new(&data[..]);
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            let result = read.peek_slice(16);
+            assert!(result.is_ok());
+            test::black_box(result.unwrap());
+
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+        }
+    });
+}
+
+/// Same as `peek_slice_peekread`, but borrows from the data
+#[bench]
+fn peek_slice_slicepeekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = SlicePeekRead::new(data.as_ref());
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            let result = read.peek_slice(16);
+            assert!(result.is_ok());
+            test::black_box(result.unwrap());
+
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+        }
+    });
+}
+
+#[bench]
+fn read_exact_normal_in_memory_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::from_slice(data.as_ref());
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            // Don't optimize me away
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+            test::black_box(result).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn read_exact_with_checkpoint_in_memory_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::from_slice(data.as_ref());
+        read.checkpoint(|read| -> Result<(), ()> {
+            let mut buf = [0; LEN];
+            for _ in 0..data.len() / 1024 {
+                use std::io::Read;
+                let result = read.read_exact(&mut buf);
+                assert!(result.is_ok());
+                test::black_box(result).unwrap();
+            }
+
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[bench]
+fn read_normal_in_memory_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::from_slice(data.as_ref());
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            // Don't optimize me away
+            let result = read.read(&mut buf);
+            assert!(result.is_ok());
+            assert_eq!(LEN, result.unwrap());
+        }
+    });
+}
+
+#[bench]
+fn read_with_checkpoint_in_memory_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::from_slice(data.as_ref());
+        read.checkpoint(|read| -> Result<(), ()> {
+            let mut buf = [0; LEN];
+            for _ in 0..data.len() / 1024 {
+                use std::io::Read;
+                let result = read.read(&mut buf);
+                assert!(result.is_ok());
+                assert_eq!(LEN, result.unwrap());
+            }
+
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+/// Same as `peek_slice_peekread`, but created with `from_slice`
+#[bench]
+fn peek_slice_in_memory_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::from_slice(data.as_ref());
+        let mut buf = [0; LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            let result = read.peek_slice(16);
+            assert!(result.is_ok());
+            test::black_box(result.unwrap());
+
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+        }
+    });
+}
+
+#[bench]
+fn read_exact_1m_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::new(Cursor::new(data.as_ref()));
+        let mut buf = vec![0; data.len()];
+        use std::io::Read;
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        test::black_box(buf);
+    });
+}
+
+#[cfg(feature = "read_buf")]
+#[bench]
+fn read_buf_exact_1m_peekread(bench: &mut Bencher) {
+    use std::io::BorrowedBuf;
+
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::new(Cursor::new(data.as_ref()));
+        let mut buf = Vec::<u8>::with_capacity(data.len());
+        let mut buf: BorrowedBuf<'_> = buf.spare_capacity_mut().into();
+        use std::io::Read;
+        let result = read.read_buf_exact(buf.unfilled());
+        assert!(result.is_ok());
+        test::black_box(buf.filled());
+    });
+}
+
+#[cfg(feature = "read_buf")]
+#[bench]
+fn read_buf_exact_peekread(bench: &mut Bencher) {
+    use std::io::BorrowedBuf;
+    use std::mem::MaybeUninit;
+
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::new(Cursor::new(data.as_ref()));
+        let mut buf = [MaybeUninit::<u8>::uninit(); LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+            // Don't optimize me away
+            let result = read.read_buf_exact(buf.unfilled());
+            assert!(result.is_ok());
+            test::black_box(buf.filled());
+        }
+    });
+}
+
+#[cfg(feature = "read_buf")]
+#[bench]
+fn peek_buf_peekread(bench: &mut Bencher) {
+    use std::io::BorrowedBuf;
+    use std::mem::MaybeUninit;
+
+    let data = generate_1m();
+
+    bench.iter(move || {
+        let mut read = PeekRead::new(Cursor::new(data.as_ref()));
+        let mut buf = [MaybeUninit::<u8>::uninit(); LEN];
+        for _ in 0..data.len() / 1024 {
+            use std::io::Read;
+            let mut peeked: BorrowedBuf<'_> = buf.as_mut_slice().into();
+            let result = read.peek_buf(peeked.unfilled());
+            assert!(result.is_ok());
+            test::black_box(peeked.filled());
+
+            let result = read.read_exact(&mut [0; LEN]);
+            assert!(result.is_ok());
+        }
+    });
+}
+
+/// Counts the reads and makes a syscall for each of them, like an unbuffered
+/// `File` or `TcpStream` would
+struct Syscall<'a> {
+    read: Cursor<&'a [u8]>,
+    reads: &'a std::cell::Cell<usize>,
+}
+
+impl std::io::Read for Syscall<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads.set(self.reads.get() + 1);
+        std::thread::yield_now();
+        return self.read.read(buf);
+    }
+}
+
+/// Peeks a 4 byte header and reads a 16 byte record (64 KiB)
+fn peek_read_records<Read: std::io::Read>(read: &mut PeekRead<Read>) {
+    use std::io::Read;
+
+    for _ in 0..64 * 1024 / 16 {
+        let mut header = [0; 4];
+        let result = read.peek_exact(&mut header);
+        assert!(result.is_ok());
+
+        let mut record = [0; 16];
+        let result = read.read_exact(&mut record);
+        assert!(result.is_ok());
+        test::black_box(record);
+    }
+}
+
+#[bench]
+fn peek_read_syscall_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+    let reads = std::cell::Cell::new(0);
+
+    bench.iter(|| {
+        let mut read = PeekRead::new(Syscall {
+            read: Cursor::new(data.as_ref()),
+            reads: &reads,
+        });
+        peek_read_records(&mut read);
+    });
+    test::black_box(reads.get());
+}
+
+#[bench]
+fn peek_read_syscall_read_ahead_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+    let reads = std::cell::Cell::new(0);
+
+    bench.iter(|| {
+        let mut read = PeekRead::new(Syscall {
+            read: Cursor::new(data.as_ref()),
+            reads: &reads,
+        })
+        .with_read_ahead(8 * 1024);
+        peek_read_records(&mut read);
+    });
+    test::black_box(reads.get());
+}
+
+#[bench]
+fn peek_read_syscall_adaptive_read_ahead_peekread(bench: &mut Bencher) {
+    let data = generate_1m();
+    let reads = std::cell::Cell::new(0);
+
+    bench.iter(|| {
+        let mut read = PeekRead::new(Syscall {
+            read: Cursor::new(data.as_ref()),
+            reads: &reads,
+        })
+        .with_adaptive_read_ahead(64, 64 * 1024);
+        peek_read_records(&mut read);
+    });
+    test::black_box(reads.get());
+}
@@ -0,0 +1,307 @@
+//! Implements `#[derive(PeekDecode)]`, use it through the `derive` feature of
+//! peekbufread.
+#![allow(clippy::needless_return)]
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+#[derive(Clone, Copy)]
+enum Endian {
+    Little,
+    Big,
+}
+
+/// Arguments of `#[peek(...)]` attributes
+#[derive(Default)]
+struct Options {
+    endian: Option<Endian>,
+    magic: Option<syn::Expr>,
+    len: Option<syn::Type>,
+    repr: Option<syn::Type>,
+    tag: Option<syn::Expr>,
+}
+
+/// Parses all `#[peek(...)]` attributes, rejects the ones not in `allowed`
+fn parse_options(attrs: &[syn::Attribute], allowed: &[&str]) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("peek")) {
+        attr.parse_nested_meta(|meta| {
+            let name = meta
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            if !allowed.contains(&name.as_str()) {
+                return Err(meta.error(format!(
+                    "unsupported peek attribute here, expected one of: {}",
+                    allowed.join(", ")
+                )));
+            }
+
+            match name.as_str() {
+                "le" => options.endian = Some(Endian::Little),
+                "be" => options.endian = Some(Endian::Big),
+                "magic" => options.magic = Some(meta.value()?.parse()?),
+                "len" => options.len = Some(meta.value()?.parse()?),
+                "repr" => options.repr = Some(meta.value()?.parse()?),
+                "tag" => options.tag = Some(meta.value()?.parse()?),
+                _ => unreachable!(),
+            }
+            return Ok(());
+        })?;
+    }
+
+    return Ok(options);
+}
+
+/// Returns the name of a primitive number type
+fn number(ty: &syn::Type) -> Option<String> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let ident = path.path.get_ident()?.to_string();
+    return match ident.as_str() {
+        "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" | "f32" | "f64" => Some(ident),
+        _ => None,
+    };
+}
+
+/// Returns the element type of `Vec<T>`
+fn vec_element(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    return match args.args.first() {
+        Some(syn::GenericArgument::Type(ty)) if args.args.len() == 1 => Some(ty),
+        _ => None,
+    };
+}
+
+/// Generates an expression, which reads a value of type `ty` from `read`
+fn read_value(ty: &syn::Type, endian: Endian) -> syn::Result<TokenStream> {
+    if let Some(number) = number(ty) {
+        let method = match (number.as_str(), endian) {
+            ("u8" | "i8", _) => format_ident!("read_{}", number),
+            (_, Endian::Little) => format_ident!("read_{}_le", number),
+            (_, Endian::Big) => format_ident!("read_{}_be", number),
+        };
+        return Ok(quote! { read.#method()? });
+    }
+
+    if let syn::Type::Array(array) = ty {
+        let len = &array.len;
+        if number(&array.elem).as_deref() == Some("u8") {
+            return Ok(quote! { read.read_array::<{ #len }>()? });
+        }
+
+        let element = read_value(&array.elem, endian)?;
+        return Ok(quote! {{
+            let mut values = ::peekbufread::__private::Vec::with_capacity(#len);
+            for _ in 0..(#len) {
+                values.push(#element);
+            }
+            match <#ty as ::core::convert::TryFrom<_>>::try_from(values) {
+                Ok(values) => values,
+                Err(_) => unreachable!(),
+            }
+        }});
+    }
+
+    if vec_element(ty).is_some() {
+        return Err(syn::Error::new(
+            ty.span(),
+            "Vec fields need a length prefix, like #[peek(len = u32)]",
+        ));
+    }
+
+    return Ok(quote! { <#ty as ::peekbufread::PeekDecode>::decode(read)? });
+}
+
+/// Generates an expression, which reads a field with its options
+fn read_field(field: &syn::Field, endian: Endian) -> syn::Result<TokenStream> {
+    let options = parse_options(&field.attrs, &["le", "be", "magic", "len"])?;
+    let endian = options.endian.unwrap_or(endian);
+    let ty = &field.ty;
+
+    let value = match (&options.len, vec_element(ty)) {
+        (Some(len), Some(element)) => {
+            let len = read_value(len, endian)?;
+            let values = if number(element).as_deref() == Some("u8") {
+                quote! { ::peekbufread::__private::read_bytes(read, len)? }
+            } else {
+                let element = read_value(element, endian)?;
+                quote! {{
+                    let mut values = ::peekbufread::__private::Vec::new();
+                    for _ in 0..len {
+                        values.push(#element);
+                    }
+                    values
+                }}
+            };
+
+            quote! {{
+                let len = ::peekbufread::__private::to_len(#len)?;
+                #values
+            }}
+        }
+        (Some(len), None) => {
+            return Err(syn::Error::new(
+                len.span(),
+                "#[peek(len = ...)] is only supported on Vec fields",
+            ));
+        }
+        (None, _) => read_value(ty, endian)?,
+    };
+
+    return Ok(match &options.magic {
+        Some(magic) => quote! {{
+            let value: #ty = #value;
+            if value != (#magic) {
+                return Err(::peekbufread::__private::invalid_data("magic value mismatch"));
+            }
+            value
+        }},
+        None => value,
+    });
+}
+
+/// Generates the construction of `path` (a struct or a variant) from its
+/// fields
+fn read_fields(
+    path: TokenStream,
+    fields: &syn::Fields,
+    endian: Endian,
+) -> syn::Result<TokenStream> {
+    return Ok(match fields {
+        syn::Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            let locals = names
+                .clone()
+                .map(|name| format_ident!("__{}", name.as_ref().unwrap()));
+            let locals_again = locals.clone();
+            let values = fields
+                .named
+                .iter()
+                .map(|field| read_field(field, endian))
+                .collect::<syn::Result<Vec<_>>>()?;
+            // Fields are read in declaration order
+            quote! {{
+                #(let #locals = #values;)*
+                #path { #(#names: #locals_again),* }
+            }}
+        }
+        syn::Fields::Unnamed(fields) => {
+            let names = (0..fields.unnamed.len()).map(|i| format_ident!("__field{}", i));
+            let values = fields
+                .unnamed
+                .iter()
+                .map(|field| read_field(field, endian))
+                .collect::<syn::Result<Vec<_>>>()?;
+            let names_again = names.clone();
+            quote! {{
+                #(let #names = #values;)*
+                #path(#(#names_again),*)
+            }}
+        }
+        syn::Fields::Unit => path,
+    });
+}
+
+fn expand(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let is_enum = matches!(input.data, syn::Data::Enum(_));
+    let allowed: &[&str] = if is_enum {
+        &["le", "be", "magic", "repr"]
+    } else {
+        &["le", "be", "magic"]
+    };
+    let options = parse_options(&input.attrs, allowed)?;
+    let endian = options.endian.unwrap_or(Endian::Big);
+
+    let magic = options.magic.as_ref().map(|magic| {
+        quote! { ::peekbufread::__private::expect_magic(read, &(#magic)[..])?; }
+    });
+
+    let body = match &input.data {
+        syn::Data::Struct(data) => read_fields(quote! { Self }, &data.fields, endian)?,
+        syn::Data::Enum(data) => {
+            let Some(repr) = &options.repr else {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "enums need the type of their discriminant, like #[peek(repr = u8)]",
+                ));
+            };
+
+            let tag = read_value(repr, endian)?;
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let variant_options = parse_options(&variant.attrs, &["tag"])?;
+                let value = match (variant_options.tag, &variant.discriminant) {
+                    (Some(tag), _) => tag,
+                    (None, Some((_, discriminant))) => discriminant.clone(),
+                    (None, None) => {
+                        return Err(syn::Error::new(
+                            variant.span(),
+                            "variants need an explicit discriminant or #[peek(tag = ...)]",
+                        ));
+                    }
+                };
+
+                let ident = &variant.ident;
+                let fields = read_fields(quote! { Self::#ident }, &variant.fields, endian)?;
+                arms.push(quote! { tag if tag == (#value) => #fields, });
+            }
+
+            quote! {{
+                let tag: #repr = #tag;
+                match tag {
+                    #(#arms)*
+                    _ => {
+                        return Err(::peekbufread::__private::invalid_data(
+                            "unknown enum discriminant",
+                        ));
+                    }
+                }
+            }}
+        }
+        syn::Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "PeekDecode can't be derived for unions",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    return Ok(quote! {
+        impl #impl_generics ::peekbufread::PeekDecode for #ident #ty_generics #where_clause {
+            fn decode<__Read: ::peekbufread::io::Read + ?Sized>(
+                read: &mut ::peekbufread::PeekRead<__Read>,
+            ) -> ::peekbufread::io::Result<Self> {
+                return read.checkpoint(|read| {
+                    #magic
+                    Ok(#body)
+                });
+            }
+        }
+    });
+}
+
+/// Derives `peekbufread::PeekDecode`, see its documentation for the
+/// supported `#[peek(...)]` attributes
+#[proc_macro_derive(PeekDecode, attributes(peek))]
+pub fn derive_peek_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    return expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into();
+}
@@ -0,0 +1,422 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Selects the async IO traits of the inner reader of an [`AsyncPeekRead`]
+pub trait AsyncIo<Read: ?Sized>: sealed::Sealed {
+    /// Reads the inner reader into `buf`
+    #[doc(hidden)]
+    fn poll_read(
+        read: Pin<&mut Read>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>>;
+}
+
+/// Inner reader implements `tokio::io::AsyncRead`
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioIo;
+
+/// Inner reader implements `futures_io::AsyncRead`
+#[cfg(feature = "futures-io")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FuturesIo;
+
+#[cfg(feature = "tokio")]
+impl<Read: tokio::io::AsyncRead + ?Sized> AsyncIo<Read> for TokioIo {
+    #[inline]
+    fn poll_read(
+        read: Pin<&mut Read>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let mut read_buf = tokio::io::ReadBuf::new(buf);
+        return match read.poll_read(cx, &mut read_buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(read_buf.filled().len())),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        };
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<Read: futures_io::AsyncRead + ?Sized> AsyncIo<Read> for FuturesIo {
+    #[inline]
+    fn poll_read(
+        read: Pin<&mut Read>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        return read.poll_read(cx, buf);
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    #[cfg(feature = "tokio")]
+    impl Sealed for super::TokioIo {}
+    #[cfg(feature = "futures-io")]
+    impl Sealed for super::FuturesIo {}
+}
+
+/// [`AsyncPeekRead`] of a `tokio::io::AsyncRead`
+#[cfg(feature = "tokio")]
+pub type TokioPeekRead<Read> = AsyncPeekRead<Read, TokioIo>;
+
+/// [`AsyncPeekRead`] of a `futures_io::AsyncRead`
+#[cfg(feature = "futures-io")]
+pub type FuturesPeekRead<Read> = AsyncPeekRead<Read, FuturesIo>;
+
+/// Peekable for async reads. Works by buffering peeked data.
+///
+/// Same semantics as [`crate::PeekRead`]. All operations are
+/// cancellation-safe: bytes are only buffered after the inner reader returned
+/// them, so dropping a future never loses any bytes. Supports
+/// `tokio::io::AsyncRead` (see [`AsyncPeekRead::new`]) and
+/// `futures_io::AsyncRead` (see `AsyncPeekRead::from_futures_io`), selected by
+/// `Io` (or the aliases `TokioPeekRead` and `FuturesPeekRead`). `Io` defaults
+/// to `TokioIo` with the feature `tokio`.
+pub struct AsyncPeekRead<
+    Read,
+    #[cfg(feature = "tokio")] Io = TokioIo,
+    #[cfg(not(feature = "tokio"))] Io,
+> {
+    ioread: Read,
+    buffer: Vec<u8>,
+    pos: usize,
+    checkpoints: Vec<usize>,
+    io: std::marker::PhantomData<Io>,
+}
+
+/// Size of the buffer filled by `poll_fill_buf`
+const FILL_BUF_SIZE: usize = 8 * 1024;
+
+#[cfg(feature = "tokio")]
+impl<Read: tokio::io::AsyncRead + Unpin> AsyncPeekRead<Read, TokioIo> {
+    pub fn new(read: Read) -> Self {
+        return Self::with_io(read);
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<Read: futures_io::AsyncRead + Unpin> AsyncPeekRead<Read, FuturesIo> {
+    pub fn from_futures_io(read: Read) -> Self {
+        return Self::with_io(read);
+    }
+}
+
+impl<Read: Unpin, Io: AsyncIo<Read>> AsyncPeekRead<Read, Io> {
+    fn with_io(read: Read) -> Self {
+        Self {
+            ioread: read,
+            buffer: Vec::new(),
+            pos: 0,
+            checkpoints: Vec::new(),
+            io: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the inner reader
+    ///
+    /// Buffered bytes, which weren't consumed yet, are lost.
+    pub fn into_inner(self) -> Read {
+        return self.ioread;
+    }
+
+    /// Returns the buffered bytes, which weren't consumed yet
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        return self.buffered();
+    }
+
+    #[inline]
+    fn buffered(&self) -> &[u8] {
+        return &self.buffer[self.pos..];
+    }
+
+    #[inline]
+    fn consume_buffer_bytes(&mut self, bytes: usize) {
+        let pos = self.pos + bytes;
+        if self.checkpoints.is_empty() {
+            if pos >= self.buffer.len() {
+                self.pos = 0;
+                self.buffer.clear();
+                return;
+            }
+
+            // Magic size (where to empty data), only if the move is cheap
+            if pos >= 128 && pos >= self.buffer.len() - pos {
+                self.buffer.drain(..pos);
+                self.pos = 0;
+                return;
+            }
+        }
+
+        self.pos = pos;
+    }
+
+    /// Appends at most `len` bytes of the inner reader to the buffer
+    ///
+    /// Nothing is appended if the inner reader is pending or fails.
+    fn poll_read_ahead(
+        &mut self,
+        cx: &mut Context<'_>,
+        len: usize,
+    ) -> Poll<std::io::Result<usize>> {
+        let start = self.buffer.len();
+        self.buffer.resize(start + len, 0);
+        loop {
+            match Io::poll_read(Pin::new(&mut self.ioread), cx, &mut self.buffer[start..]) {
+                Poll::Ready(Ok(consumed_reader)) => {
+                    self.buffer.truncate(start + consumed_reader);
+                    return Poll::Ready(Ok(consumed_reader));
+                }
+                Poll::Ready(Err(err)) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(err)) => {
+                    self.buffer.truncate(start);
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Pending => {
+                    self.buffer.truncate(start);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+
+    /// Reads from the inner reader until at least `len` bytes are buffered
+    ///
+    /// Bytes read before the inner reader was pending or failed stay buffered.
+    fn poll_fill_buffer(&mut self, cx: &mut Context<'_>, len: usize) -> Poll<std::io::Result<()>> {
+        while self.buffer.len() - self.pos < len {
+            let missing = self.pos + len - self.buffer.len();
+            match self.poll_read_ahead(cx, missing) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    )));
+                }
+                Poll::Ready(Ok(_)) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        return Poll::Ready(Ok(()));
+    }
+
+    /// Reads into `buf`, shared by the `AsyncRead` implementations
+    fn poll_read_into(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.buffered().is_empty() && !buf.is_empty() {
+            if self.checkpoints.is_empty() {
+                // Nothing to record, read directly
+                return Io::poll_read(Pin::new(&mut self.ioread), cx, buf);
+            }
+
+            match self.poll_read_ahead(cx, buf.len()) {
+                Poll::Ready(Ok(_)) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let consumed = buf.len().min(self.buffered().len());
+        buf[..consumed].copy_from_slice(&self.buffered()[..consumed]);
+        self.consume_buffer_bytes(consumed);
+        return Poll::Ready(Ok(consumed));
+    }
+
+    /// Fills the buffer if it is empty, shared by the `AsyncBufRead`
+    /// implementations
+    fn poll_fill_buf_inner(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        if self.buffered().is_empty() {
+            match self.poll_read_ahead(cx, FILL_BUF_SIZE) {
+                Poll::Ready(Ok(_)) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        return Poll::Ready(Ok(self.buffered()));
+    }
+
+    /// Peeks the stream
+    ///
+    /// Returns the top of the stream without consuming its contents. Reads
+    /// the inner reader only if nothing is buffered.
+    pub async fn peek(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.buffered().is_empty() && !buf.is_empty() {
+            std::future::poll_fn(|cx| self.poll_read_ahead(cx, buf.len())).await?;
+        }
+
+        let consumed = buf.len().min(self.buffered().len());
+        buf[..consumed].copy_from_slice(&self.buffered()[..consumed]);
+        return Ok(consumed);
+    }
+
+    /// Peeks the stream
+    ///
+    /// Returns the top of the stream without consuming its contents
+    pub async fn peek_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        buf.copy_from_slice(self.peek_slice(buf.len()).await?);
+        return Ok(());
+    }
+
+    /// Peeks exactly `len` bytes of the stream
+    ///
+    /// Like [`AsyncPeekRead::peek_exact`], but borrows the bytes from the
+    /// buffer.
+    pub async fn peek_slice(&mut self, len: usize) -> std::io::Result<&[u8]> {
+        std::future::poll_fn(|cx| self.poll_fill_buffer(cx, len)).await?;
+        return Ok(&self.buffered()[..len]);
+    }
+
+    /// Creates a checkpoint and calls fn_checkpoint afterwards
+    ///
+    /// Resets the reader to the current state if an Error is returned. Can be
+    /// stacked. `fn_checkpoint` can't await, so peek the required bytes first
+    /// and parse [`AsyncPeekRead::buffer`] (or use
+    /// [`AsyncPeekRead::checkpoint_guard`], or `checkpoint_async` with the
+    /// feature `async-closures`).
+    pub fn checkpoint<T, E, F: FnOnce(&mut Self) -> Result<T, E>>(
+        &mut self,
+        fn_checkpoint: F,
+    ) -> Result<T, E> {
+        let mut guard = self.checkpoint_guard();
+        let result = fn_checkpoint(&mut guard);
+        if result.is_ok() {
+            guard.commit();
+        }
+
+        return result;
+    }
+
+    /// Creates a checkpoint and awaits fn_checkpoint afterwards
+    ///
+    /// Resets the reader to the current state if an Error is returned, or if
+    /// the returned future is dropped before it completed. Can be stacked.
+    /// Requires the feature `async-closures` (Rust 1.85).
+    #[cfg(feature = "async-closures")]
+    pub async fn checkpoint_async<T, E, F: AsyncFnOnce(&mut Self) -> Result<T, E>>(
+        &mut self,
+        fn_checkpoint: F,
+    ) -> Result<T, E> {
+        let mut guard = self.checkpoint_guard();
+        let result = fn_checkpoint(&mut guard).await;
+        if result.is_ok() {
+            guard.commit();
+        }
+
+        return result;
+    }
+
+    /// Creates a checkpoint, which can be held across `.await`
+    ///
+    /// The reader is reset to the current state when the guard is dropped,
+    /// unless [`CheckpointGuard::commit`] was called.
+    pub fn checkpoint_guard(&mut self) -> CheckpointGuard<'_, Read, Io> {
+        self.checkpoints.push(self.pos);
+        return CheckpointGuard { read: self };
+    }
+}
+
+/// Checkpoint of an [`AsyncPeekRead`], see [`AsyncPeekRead::checkpoint_guard`]
+///
+/// Dereferences to the reader. Rolls the reader back when dropped without
+/// being committed, e.g. because the enclosing future was dropped.
+pub struct CheckpointGuard<'a, Read: Unpin, Io: AsyncIo<Read>> {
+    read: &'a mut AsyncPeekRead<Read, Io>,
+}
+
+impl<Read: Unpin, Io: AsyncIo<Read>> CheckpointGuard<'_, Read, Io> {
+    /// Keeps everything consumed since the checkpoint was created
+    pub fn commit(self) {
+        self.read.checkpoints.pop();
+        std::mem::forget(self);
+    }
+}
+
+impl<Read: Unpin, Io: AsyncIo<Read>> Drop for CheckpointGuard<'_, Read, Io> {
+    fn drop(&mut self) {
+        self.read.pos = self.read.checkpoints.pop().unwrap();
+    }
+}
+
+impl<Read: Unpin, Io: AsyncIo<Read>> std::ops::Deref for CheckpointGuard<'_, Read, Io> {
+    type Target = AsyncPeekRead<Read, Io>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        return self.read;
+    }
+}
+
+impl<Read: Unpin, Io: AsyncIo<Read>> std::ops::DerefMut for CheckpointGuard<'_, Read, Io> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        return self.read;
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<Read: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for AsyncPeekRead<Read, TokioIo> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        return match self.get_mut().poll_read_into(cx, buf.initialize_unfilled()) {
+            Poll::Ready(Ok(consumed)) => {
+                buf.advance(consumed);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        };
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<Read: tokio::io::AsyncRead + Unpin> tokio::io::AsyncBufRead for AsyncPeekRead<Read, TokioIo> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        return self.get_mut().poll_fill_buf_inner(cx);
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = amt.min(this.buffered().len());
+        this.consume_buffer_bytes(amt);
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<Read: futures_io::AsyncRead + Unpin> futures_io::AsyncRead for AsyncPeekRead<Read, FuturesIo> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        return self.get_mut().poll_read_into(cx, buf);
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<Read: futures_io::AsyncRead + Unpin> futures_io::AsyncBufRead
+    for AsyncPeekRead<Read, FuturesIo>
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        return self.get_mut().poll_fill_buf_inner(cx);
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = amt.min(this.buffered().len());
+        this.consume_buffer_bytes(amt);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::PeekRead;
+
+/// Selects the bit order of a [`BitPeekRead`]
+pub trait BitOrder: sealed::Sealed {
+    /// Whether the most significant bit of a byte comes first
+    const MSB_FIRST: bool;
+}
+
+/// Most significant bit first (like H.264 or JPEG), the default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsbFirst;
+
+/// Least significant bit first (like DEFLATE)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LsbFirst;
+
+impl BitOrder for MsbFirst {
+    const MSB_FIRST: bool = true;
+}
+
+impl BitOrder for LsbFirst {
+    const MSB_FIRST: bool = false;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::MsbFirst {}
+    impl Sealed for super::LsbFirst {}
+}
+
+/// Peekable for bits, works on top of a [`PeekRead`]
+///
+/// Values of several bits are composed in the bit order: with [`MsbFirst`]
+/// the first bit is the most significant one of the value, with
+/// [`LsbFirst`] the least significant one.
+pub struct BitPeekRead<Read: crate::io::Read + ?Sized, Order: BitOrder = MsbFirst> {
+    /// Bits of the first buffered byte, which were consumed already
+    bit: u32,
+    order: core::marker::PhantomData<Order>,
+    read: PeekRead<Read>,
+}
+
+impl<Read: crate::io::Read> BitPeekRead<Read, MsbFirst> {
+    pub fn new(read: Read) -> Self {
+        return Self::from_peek_read(PeekRead::new(read));
+    }
+}
+
+impl<Read: crate::io::Read> BitPeekRead<Read, LsbFirst> {
+    /// Creates a BitPeekRead, which reads the least significant bit first
+    pub fn lsb_first(read: Read) -> Self {
+        return Self::from_peek_read(PeekRead::new(read));
+    }
+}
+
+impl<Read: crate::io::Read, Order: BitOrder> BitPeekRead<Read, Order> {
+    /// Reads bits from `read`, starting at a byte boundary
+    pub fn from_peek_read(read: PeekRead<Read>) -> Self {
+        return Self {
+            bit: 0,
+            order: core::marker::PhantomData,
+            read,
+        };
+    }
+
+    /// Returns the PeekRead
+    ///
+    /// A partially consumed byte is still part of the PeekRead.
+    pub fn into_inner(self) -> PeekRead<Read> {
+        return self.read;
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Order: BitOrder> BitPeekRead<Read, Order> {
+    /// Returns the number of consumed bits
+    #[inline]
+    pub fn bit_position(&self) -> u64 {
+        return self.read.position() * 8 + self.bit as u64;
+    }
+
+    /// Peeks the next `n` bits (at most 64)
+    ///
+    /// Fails with `UnexpectedEof` if the stream has less than `n` bits left.
+    pub fn peek_bits(&mut self, n: u32) -> crate::io::Result<u64> {
+        assert!(n <= 64, "can't peek more than 64 bits at once");
+        if n == 0 {
+            return Ok(0);
+        }
+
+        let len = (self.bit + n).div_ceil(8) as usize;
+        let bytes = self.read.peek_slice(len)?;
+
+        let mut acc = 0u128;
+        let value = if Order::MSB_FIRST {
+            for byte in bytes {
+                acc = (acc << 8) | *byte as u128;
+            }
+            acc >> (len as u32 * 8 - self.bit - n)
+        } else {
+            for byte in bytes.iter().rev() {
+                acc = (acc << 8) | *byte as u128;
+            }
+            acc >> self.bit
+        };
+
+        return Ok((value & ((1u128 << n) - 1)) as u64);
+    }
+
+    /// Reads the next `n` bits (at most 64)
+    ///
+    /// Nothing is consumed if the stream has less than `n` bits left.
+    pub fn read_bits(&mut self, n: u32) -> crate::io::Result<u64> {
+        let value = self.peek_bits(n)?;
+        self.consume_bits(n);
+        return Ok(value);
+    }
+
+    /// Reads a single bit
+    pub fn read_bit(&mut self) -> crate::io::Result<bool> {
+        return Ok(self.read_bits(1)? == 1);
+    }
+
+    #[inline]
+    fn consume_bits(&mut self, n: u32) {
+        let bits = self.bit + n;
+        self.read.consume_buffer_bytes((bits / 8) as usize);
+        self.bit = bits % 8;
+    }
+
+    /// Skips the rest of a partially consumed byte
+    ///
+    /// Returns the number of skipped bits.
+    pub fn align_to_byte(&mut self) -> u32 {
+        if self.bit == 0 {
+            return 0;
+        }
+
+        let skipped = 8 - self.bit;
+        self.consume_bits(skipped);
+        return skipped;
+    }
+
+    /// Creates a checkpoint and calls fn_checkpoint afterwards
+    ///
+    /// Resets the reader to the current bit if an Error is returned. Can be
+    /// stacked.
+    pub fn checkpoint<T, E, F: FnOnce(&mut Self) -> Result<T, E>>(
+        &mut self,
+        fn_checkpoint: F,
+    ) -> Result<T, E> {
+        let bit = self.bit;
+        self.read.push_checkpoint();
+
+        let result = fn_checkpoint(self);
+        self.read.pop_checkpoint(result.is_err());
+        if result.is_err() {
+            self.bit = bit;
+        }
+        return result;
+    }
+}
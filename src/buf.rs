@@ -0,0 +1,102 @@
+use crate::{CheckpointMode, PeekRead};
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Peeks exactly `len` bytes of the stream as `Bytes`
+    ///
+    /// Copies the bytes, because they stay buffered. The result can outlive
+    /// the borrow of the reader.
+    pub fn peek_bytes(&mut self, len: usize) -> crate::io::Result<bytes::Bytes> {
+        return Ok(bytes::Bytes::copy_from_slice(self.peek_slice(len)?));
+    }
+
+    /// Reads exactly `len` bytes of the stream as `Bytes`
+    ///
+    /// Splits the bytes off the buffer without copying them, unless a
+    /// checkpoint is active (it must be able to roll back) or the data is in
+    /// memory (see [`PeekRead::from_slice`]). Nothing is consumed if the
+    /// stream ended before (like [`PeekRead::peek_exact`]).
+    pub fn read_bytes(&mut self, len: usize) -> crate::io::Result<bytes::Bytes> {
+        if !self.is_checkpoint_empty() || self.memory.is_some() {
+            let result = self.peek_bytes(len)?;
+            self.consume_buffer_bytes(len);
+            return Ok(result);
+        }
+
+        self.fill_buffer(len)?;
+        let pos = self.pos.unwrap_or(0);
+        let mut result = self.buffer.split_to(pos + len);
+        bytes::Buf::advance(&mut result, pos);
+        self.discarded += (pos + len) as u64;
+        self.pos = if self.buffer.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        return Ok(result.freeze());
+    }
+
+    /// Returns a `Buf` of the bytes, which are buffered already
+    ///
+    /// Advancing the `Buf` consumes the bytes of the reader. Doesn't read the
+    /// inner reader.
+    pub fn lookahead(&mut self) -> Lookahead<'_, Read, Mode> {
+        return Lookahead { read: self };
+    }
+}
+
+impl<Read: crate::io::Read, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Reads `leftover` before the inner reader
+    ///
+    /// Useful if bytes of the inner reader were already read into a
+    /// `BytesMut` (e.g. by a codec). In-memory data (see
+    /// [`PeekRead::from_slice`]) is copied into the buffer behind `leftover`.
+    pub fn with_leftover(mut self, leftover: bytes::BytesMut) -> Self {
+        if leftover.is_empty() {
+            return self;
+        }
+
+        if self.memory.take().is_some() {
+            // The inner reader wasn't read yet, so its data can be buffered
+            while matches!(self.read_chunk(crate::COPY_SIZE), Ok(len) if len != 0) {}
+        }
+
+        // Doesn't copy `leftover` if nothing is buffered yet
+        let pos = self.pos.unwrap_or(0);
+        let tail = self.buffer.split_off(pos);
+        self.buffer.unsplit(leftover);
+        self.buffer.unsplit(tail);
+        self.pos = Some(pos);
+        return self;
+    }
+}
+
+/// Buffered bytes of a [`PeekRead`], see [`PeekRead::lookahead`]
+pub struct Lookahead<'a, Read: crate::io::Read + ?Sized, Mode: CheckpointMode> {
+    read: &'a mut PeekRead<Read, Mode>,
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> bytes::Buf
+    for Lookahead<'_, Read, Mode>
+{
+    #[inline]
+    fn remaining(&self) -> usize {
+        return self.chunk().len();
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        return match self.read.pos {
+            Some(pos) => &self.read.data()[pos..],
+            None => &[],
+        };
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining(),
+            "cannot advance past the buffered bytes"
+        );
+        self.read.consume_buffer_bytes(cnt);
+    }
+}
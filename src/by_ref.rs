@@ -0,0 +1,118 @@
+use crate::{CheckpointMode, Checkpoints, PeekRead};
+use alloc::vec::Vec;
+
+/// [`PeekRead`] over a borrowed reader, see [`PeekRead::by_ref_with`]
+///
+/// Hands the lookahead, which wasn't consumed yet, back to the caller when
+/// dropped or finished.
+pub struct PeekReadRef<'a, Read: crate::io::Read + ?Sized, Mode: CheckpointMode = Checkpoints> {
+    peek_read: PeekRead<&'a mut Read, Mode>,
+    leftover: &'a mut Vec<u8>,
+}
+
+impl<'a, Read: crate::io::Read + ?Sized> PeekRead<&'a mut Read, Checkpoints> {
+    /// Creates a [`PeekRead`], which borrows `reader`
+    ///
+    /// Peeking may read more bytes from `reader` than are consumed. Those
+    /// bytes are stored in `leftover` when the returned reader is dropped (or
+    /// [`PeekReadRef::finish`] is called). Bytes already in `leftover` are
+    /// read first, so the same `leftover` can be passed to the next borrow.
+    pub fn by_ref_with(
+        reader: &'a mut Read,
+        leftover: &'a mut Vec<u8>,
+    ) -> PeekReadRef<'a, Read, Checkpoints> {
+        let mut peek_read = PeekRead::new(reader);
+        peek_read.buffer = crate::buffer_from_vec(core::mem::take(leftover));
+        if !peek_read.buffer.is_empty() {
+            peek_read.pos = Some(0);
+        }
+
+        return PeekReadRef {
+            peek_read,
+            leftover,
+        };
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekReadRef<'_, Read, Mode> {
+    /// Ends the borrow and stores the leftover lookahead
+    pub fn finish(self) {
+        // Dropping stores the leftover lookahead
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> Drop for PeekReadRef<'_, Read, Mode> {
+    fn drop(&mut self) {
+        let buffer = &mut self.peek_read.buffer;
+        match self.peek_read.pos.take() {
+            Some(pos) => crate::discard_front(buffer, pos),
+            None => buffer.clear(),
+        }
+
+        *self.leftover = crate::buffer_into_vec(core::mem::take(buffer));
+    }
+}
+
+impl<'a, Read: crate::io::Read + ?Sized, Mode: CheckpointMode> core::ops::Deref
+    for PeekReadRef<'a, Read, Mode>
+{
+    type Target = PeekRead<&'a mut Read, Mode>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        return &self.peek_read;
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> core::ops::DerefMut
+    for PeekReadRef<'_, Read, Mode>
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        return &mut self.peek_read;
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> crate::io::Read
+    for PeekReadRef<'_, Read, Mode>
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        return self.peek_read.read(buf);
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        return self.peek_read.read_exact(buf);
+    }
+
+    #[cfg(feature = "std")]
+    fn read_vectored(&mut self, bufs: &mut [std::io::IoSliceMut<'_>]) -> crate::io::Result<usize> {
+        return self.peek_read.read_vectored(bufs);
+    }
+
+    #[cfg(feature = "std")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> crate::io::Result<usize> {
+        return self.peek_read.read_to_end(buf);
+    }
+
+    #[cfg(feature = "std")]
+    fn read_to_string(&mut self, buf: &mut String) -> crate::io::Result<usize> {
+        return self.peek_read.read_to_string(buf);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Read: std::io::BufRead + ?Sized, Mode: CheckpointMode> std::io::BufRead
+    for PeekReadRef<'_, Read, Mode>
+{
+    #[inline]
+    fn fill_buf(&mut self) -> crate::io::Result<&[u8]> {
+        return self.peek_read.fill_buf();
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.peek_read.consume(amt);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{CheckpointMode, PeekRead};
+
+/// Maximum alignment supported by [`PeekRead::peek_as`]
+const MAX_ALIGN: usize = 64;
+
+/// Storage for copies of misaligned values
+#[derive(Clone, Copy)]
+#[repr(C, align(64))]
+pub(crate) struct Aligned([u8; MAX_ALIGN]);
+
+// Safety: Aligned is plain bytes without padding
+unsafe impl bytemuck::Zeroable for Aligned {}
+unsafe impl bytemuck::Pod for Aligned {}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Peeks the stream as `T`
+    ///
+    /// Borrows from the buffer if it's aligned for `T`, otherwise the bytes
+    /// are copied to an aligned place first. `T` must not be aligned to more
+    /// than 64 bytes.
+    pub fn peek_as<T: bytemuck::AnyBitPattern>(&mut self) -> crate::io::Result<&T> {
+        const {
+            assert!(
+                core::mem::align_of::<T>() <= MAX_ALIGN,
+                "peek_as supports alignments up to 64 bytes"
+            )
+        };
+
+        let size = core::mem::size_of::<T>();
+        self.fill_buffer(size)?;
+
+        let pos = self.pos.unwrap_or(0);
+        let align_offset = self.data()[pos..]
+            .as_ptr()
+            .align_offset(core::mem::align_of::<T>());
+        if align_offset == 0 {
+            return Ok(bytemuck::from_bytes(&self.data()[pos..(pos + size)]));
+        }
+
+        // Misaligned: copy to the aligned storage
+        let blocks = size.div_ceil(MAX_ALIGN);
+        let mut aligned = core::mem::take(&mut self.aligned);
+        aligned.clear();
+        aligned.resize(blocks, Aligned([0; MAX_ALIGN]));
+        bytemuck::cast_slice_mut::<Aligned, u8>(aligned.as_mut_slice())[..size]
+            .copy_from_slice(&self.data()[pos..(pos + size)]);
+        self.aligned = aligned;
+
+        let aligned: &[u8] = bytemuck::cast_slice(self.aligned.as_slice());
+        return Ok(bytemuck::from_bytes(&aligned[..size]));
+    }
+
+    /// Reads `T` from the stream
+    ///
+    /// Nothing is consumed if the stream ended before.
+    pub fn read_as<T: bytemuck::AnyBitPattern>(&mut self) -> crate::io::Result<T> {
+        let size = core::mem::size_of::<T>();
+        let result = bytemuck::pod_read_unaligned(self.peek_slice(size)?);
+        self.consume_buffer_bytes(size);
+        return Ok(result);
+    }
+}
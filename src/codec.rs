@@ -0,0 +1,67 @@
+use crate::PeekRead;
+
+/// Parser of a [`PeekRead`], which can also be used as a `Decoder`
+///
+/// Implementations are generic over the inner reader, so the same parser
+/// serves sync [`PeekRead`]s and codecs (see `PeekDecoder` of the feature
+/// `tokio-util`).
+pub trait PeekParser {
+    type Item;
+
+    /// Parses the next item of `read`
+    ///
+    /// Returns an error of kind `UnexpectedEof` if `read` ended before the
+    /// item was complete.
+    fn parse<Read: crate::io::Read + ?Sized>(
+        &mut self,
+        read: &mut PeekRead<Read>,
+    ) -> crate::io::Result<Self::Item>;
+}
+
+/// `tokio_util::codec::Decoder` for a [`PeekParser`]
+///
+/// Parses the received bytes. If the parser fails with `UnexpectedEof`, the
+/// bytes are left untouched and `None` is returned until more bytes arrive.
+/// Otherwise the bytes the parser consumed are removed.
+#[cfg(feature = "tokio-util")]
+#[derive(Debug, Clone, Default)]
+pub struct PeekDecoder<Parser: PeekParser> {
+    parser: Parser,
+}
+
+#[cfg(feature = "tokio-util")]
+impl<Parser: PeekParser> PeekDecoder<Parser> {
+    pub fn new(parser: Parser) -> Self {
+        return Self { parser };
+    }
+
+    /// Returns the parser
+    pub fn into_inner(self) -> Parser {
+        return self.parser;
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl<Parser: PeekParser> tokio_util::codec::Decoder for PeekDecoder<Parser> {
+    type Item = Parser::Item;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> std::io::Result<Option<Self::Item>> {
+        let mut remaining = &src[..];
+        let mut leftover = alloc::vec::Vec::new();
+        let mut read = PeekRead::by_ref_with(&mut remaining, &mut leftover);
+        let result = self.parser.parse(&mut read);
+        read.finish();
+
+        match result {
+            Ok(item) => {
+                // Peeked but unconsumed bytes are still part of the stream
+                let consumed = src.len() - remaining.len() - leftover.len();
+                bytes::Buf::advance(src, consumed);
+                return Ok(Some(item));
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+    }
+}
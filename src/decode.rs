@@ -0,0 +1,77 @@
+use crate::PeekRead;
+
+/// Decodes a value from a [`PeekRead`], usually implemented with
+/// `#[derive(PeekDecode)]`
+///
+/// Derived implementations decode inside a checkpoint, so nothing is
+/// consumed if decoding fails.
+///
+/// # Attributes
+///
+/// - `#[peek(le)]`/`#[peek(be)]` on the type or on a field selects the byte
+///   order of numbers (default: big-endian)
+/// - `#[peek(magic = b"...")]` on the type expects these bytes before the
+///   first field, on a field it expects the field to equal the value
+/// - `#[peek(len = u16)]` on a `Vec` field reads its length as prefix first
+/// - `#[peek(repr = u8)]` on an enum reads the discriminant, variants use
+///   their explicit discriminant or `#[peek(tag = ...)]`
+///
+/// Mismatching magic values and unknown discriminants fail with
+/// `InvalidData`.
+pub trait PeekDecode: Sized {
+    fn decode<Read: crate::io::Read + ?Sized>(read: &mut PeekRead<Read>)
+        -> crate::io::Result<Self>;
+}
+
+/// Used by the code `#[derive(PeekDecode)]` generates
+#[doc(hidden)]
+pub mod __private {
+    use crate::{CheckpointMode, PeekRead};
+    pub use alloc::vec::Vec;
+
+    #[inline]
+    pub fn invalid_data(message: &'static str) -> crate::io::Error {
+        return crate::io::Error::new(crate::io::ErrorKind::InvalidData, message);
+    }
+
+    pub fn expect_magic<Read: crate::io::Read + ?Sized, Mode: CheckpointMode>(
+        read: &mut PeekRead<Read, Mode>,
+        magic: &[u8],
+    ) -> crate::io::Result<()> {
+        if read.peek_slice(magic.len())? != magic {
+            return Err(invalid_data("magic value mismatch"));
+        }
+
+        read.consume_buffer_bytes(magic.len());
+        return Ok(());
+    }
+
+    /// Bytes read at once by [`read_bytes`]
+    const READ_BYTES_CHUNK: usize = 64 * 1024;
+
+    /// Reads `len` bytes of a length-prefixed field
+    ///
+    /// Reads in chunks, so a bogus length prefix fails at the end of the
+    /// stream instead of allocating the whole length up front. The derived
+    /// code runs inside a checkpoint, which restores the consumed chunks.
+    pub fn read_bytes<Read: crate::io::Read + ?Sized, Mode: CheckpointMode>(
+        read: &mut PeekRead<Read, Mode>,
+        len: usize,
+    ) -> crate::io::Result<Vec<u8>> {
+        let mut result = Vec::new();
+        while result.len() < len {
+            let chunk = (len - result.len()).min(READ_BYTES_CHUNK);
+            result.extend_from_slice(read.peek_slice(chunk)?);
+            read.consume_buffer_bytes(chunk);
+        }
+
+        return Ok(result);
+    }
+
+    #[inline]
+    pub fn to_len<Len: TryInto<usize>>(len: Len) -> crate::io::Result<usize> {
+        return len
+            .try_into()
+            .map_err(|_| invalid_data("length prefix exceeds usize"));
+    }
+}
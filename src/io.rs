@@ -0,0 +1,143 @@
+//! IO traits and errors used by [`crate::PeekRead`]
+//!
+//! With the feature `std` (the default), these are the ones of `std::io`.
+//! Without it, this module provides a minimal replacement for `no_std` +
+//! `alloc` targets: implement [`Read`] for the inner reader.
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind, Read, Result};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::{Error, ErrorKind, Read, Result};
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::boxed::Box;
+
+    /// Result of IO operations
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Kind of an [`Error`], a subset of `std::io::ErrorKind`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum ErrorKind {
+        /// The operation was interrupted and can be retried
+        Interrupted,
+        /// The reader isn't ready, the operation can be repeated later
+        WouldBlock,
+        /// The stream ended before enough bytes were read
+        UnexpectedEof,
+        /// An argument was invalid
+        InvalidInput,
+        /// The data of the stream was invalid
+        InvalidData,
+        /// Any other error
+        Other,
+    }
+
+    impl ErrorKind {
+        fn as_str(&self) -> &'static str {
+            return match self {
+                ErrorKind::Interrupted => "operation interrupted",
+                ErrorKind::WouldBlock => "operation would block",
+                ErrorKind::UnexpectedEof => "unexpected end of file",
+                ErrorKind::InvalidInput => "invalid input parameter",
+                ErrorKind::InvalidData => "invalid data",
+                ErrorKind::Other => "other error",
+            };
+        }
+    }
+
+    /// Error of IO operations
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+        message: &'static str,
+    }
+
+    impl Error {
+        pub fn new(kind: ErrorKind, message: &'static str) -> Self {
+            return Self { kind, message };
+        }
+
+        pub fn kind(&self) -> ErrorKind {
+            return self.kind;
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            return Self::new(kind, kind.as_str());
+        }
+    }
+
+    impl core::fmt::Display for Error {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            return f.write_str(self.message);
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    /// Minimal replacement for `std::io::Read`
+    pub trait Read {
+        /// Reads at most `buf.len()` bytes, returns 0 at the end of the stream
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+        /// Reads exactly `buf.len()` bytes
+        fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.read(buf) {
+                    Ok(0) => break,
+                    Ok(consumed) => buf = &mut buf[consumed..],
+                    Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if !buf.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
+            }
+
+            return Ok(());
+        }
+    }
+
+    impl Read for &[u8] {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let consumed = buf.len().min(self.len());
+            let (data, rest) = self.split_at(consumed);
+            buf[..consumed].copy_from_slice(data);
+            *self = rest;
+            return Ok(consumed);
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            return (**self).read(buf);
+        }
+
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            return (**self).read_exact(buf);
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for Box<R> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            return (**self).read(buf);
+        }
+
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            return (**self).read_exact(buf);
+        }
+    }
+}
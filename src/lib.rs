@@ -1,14 +1,21 @@
 //! Allows to peek data of abitrary `std::io::Read` and comes with supports for
 //! checkpoints. Both features work by buffering parts of the original stream.
 //!
-//! This crate is intentionally kept very simple: it only offers the struct
-//! PeekRead and nothing on top of it.
+//! This crate is intentionally kept very simple: it mainly offers the struct
+//! PeekRead. [`PrefetchPeekRead`] is a PeekRead, which reads the original
+//! stream ahead on a background thread. [`PeekRead::from_slice`] peeks
+//! in-memory data without copying, [`SlicePeekRead`] also lends out slices
+//! which outlive it. [`PeekRead::by_ref_with`] borrows a
+//! reader and hands the unconsumed lookahead back afterwards. With the
+//! feature `tokio` (or `futures-io`), `AsyncPeekRead` peeks async readers.
+//! With the feature `tokio-util`, `PeekDecoder` turns a parser of PeekRead into
+//! a `Decoder`.
 //!
 //! ## Example
 //!
 //! ```rust
 //! use peekbufread::PeekRead;
-//! use std::io::Read;
+//! use peekbufread::io::Read;
 //!
 //! let test = b"hello, world";
 //! let mut read = PeekRead::new(test.as_ref());
@@ -19,235 +26,1073 @@
 //! let mut buf = [0; 12];
 //! read.read(&mut buf).ok();
 //! ```
+//!
+//! ## Non-blocking readers
+//!
+//! Errors of kind `Interrupted` are always retried. If the inner reader
+//! returns `WouldBlock` (or any other error) in the middle of
+//! [`PeekRead::peek_exact`] or `read_exact`, all bytes read so far stay
+//! buffered and nothing is consumed, so the call can just be repeated.
+//! [`PeekRead::try_peek_exact`] returns `None` instead of `WouldBlock`.
+//!
+//! Errors are never swallowed: if the inner reader fails after `read` took
+//! some buffered bytes, these bytes are returned and the error is returned by
+//! the next call. `peek` returns the error even if some bytes are buffered,
+//! they stay buffered.
+//!
+//! ## no_std
+//!
+//! Without the default feature `std`, the crate only requires `alloc`. The
+//! inner reader then implements the minimal [`io::Read`] of this crate.
+//! Peeking and checkpoints work the same, std-only parts (like vectored reads,
+//! `BufRead` or `PrefetchPeekRead`) are unavailable.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "read_buf", feature(core_io_borrowed_buf, read_buf))]
+#![allow(clippy::needless_return)]
+
+extern crate alloc;
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+mod async_read;
+mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
+mod by_ref;
+#[cfg(feature = "bytemuck")]
+mod cast;
+mod codec;
+#[cfg(feature = "derive")]
+mod decode;
+pub mod io;
+mod lines;
+mod mode;
+#[cfg(feature = "aho-corasick")]
+mod patterns;
+#[cfg(feature = "std")]
+mod prefetch;
+#[cfg(feature = "regex")]
+mod regex;
+mod search;
+mod slice;
+mod sniff;
+mod traits;
+mod typed;
+mod varint;
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub use async_read::{AsyncIo, AsyncPeekRead, CheckpointGuard};
+#[cfg(feature = "futures-io")]
+pub use async_read::{FuturesIo, FuturesPeekRead};
+#[cfg(feature = "tokio")]
+pub use async_read::{TokioIo, TokioPeekRead};
+pub use bits::{BitOrder, BitPeekRead, LsbFirst, MsbFirst};
+#[cfg(feature = "bytes")]
+pub use buf::Lookahead;
+pub use by_ref::PeekReadRef;
+#[cfg(feature = "tokio-util")]
+pub use codec::PeekDecoder;
+pub use codec::PeekParser;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use decode::__private;
+#[cfg(feature = "derive")]
+pub use decode::PeekDecode;
+pub use lines::{LineError, LinesWithOffsets};
+pub use mode::{CheckpointMode, Checkpoints, NoCheckpoints};
+#[cfg(feature = "aho-corasick")]
+pub use patterns::Patterns;
+#[cfg(feature = "derive")]
+pub use peekbufread_derive::PeekDecode;
+#[cfg(feature = "std")]
+pub use prefetch::{Prefetch, PrefetchPeekRead};
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+pub use slice::SlicePeekRead;
+pub use sniff::{Format, Sniffer};
+pub use traits::{Checkpoint, Mark, Peek};
+
+use alloc::vec::Vec;
+
+/// Buffer of a [`PeekRead`]
+///
+/// With the feature `bytes`, `read_bytes` splits bytes off without copying.
+/// The feature switches the buffer of every PeekRead in the build, including
+/// the ones of other crates (features are unified by cargo).
+#[cfg(feature = "bytes")]
+type Buffer = bytes::BytesMut;
+#[cfg(not(feature = "bytes"))]
+type Buffer = Vec<u8>;
+
+/// Removes the first `len` bytes of `buffer`
+#[inline]
+fn discard_front(buffer: &mut Buffer, len: usize) {
+    #[cfg(feature = "bytes")]
+    bytes::Buf::advance(buffer, len);
+    #[cfg(not(feature = "bytes"))]
+    buffer.drain(..len);
+}
+
+/// Converts `vec` to a buffer without copying
+#[inline]
+fn buffer_from_vec(vec: Vec<u8>) -> Buffer {
+    #[cfg(feature = "bytes")]
+    return bytes::Bytes::from(vec).into();
+    #[cfg(not(feature = "bytes"))]
+    return vec;
+}
+
+/// Converts `buffer` to a `Vec`, copies only if its bytes are shared
+#[inline]
+fn buffer_into_vec(buffer: Buffer) -> Vec<u8> {
+    #[cfg(feature = "bytes")]
+    return buffer.into();
+    #[cfg(not(feature = "bytes"))]
+    return buffer;
+}
+
+/// Bytes read from the inner reader at once by `copy_to`
+#[cfg(feature = "std")]
+const COPY_SIZE: usize = 8 * 1024;
 
 /// Peekable for IO-read. Works by buffering peeked data.
-/// Also supports checkpoints.
-pub struct PeekRead<Read: std::io::Read> {
-    ioread: Read,
-    buffer: Vec<u8>,
+/// Also supports checkpoints (see [`CheckpointMode`]).
+///
+/// The inner reader may be unsized, e.g. `PeekRead<dyn Read + Send>` (behind a
+/// pointer like `Box`).
+///
+/// In-memory data is only peeked without copying if the PeekRead was created
+/// with [`PeekRead::from_slice`] or [`PeekRead::from_cursor`], `new` copies
+/// it into the buffer like any other stream.
+pub struct PeekRead<Read: crate::io::Read + ?Sized, Mode: CheckpointMode = Checkpoints> {
+    buffer: Buffer,
     pos: Option<usize>,
-    #[cfg(feature = "checkpoint")]
+    /// Set for in-memory data, which is used instead of the buffer
+    memory: Option<Memory>,
+    chunk: ChunkSize,
     checkpoints: Vec<usize>,
+    mode: core::marker::PhantomData<Mode>,
+    /// Bytes of the stream, which were consumed and aren't buffered anymore
+    discarded: u64,
+    /// Error of the inner reader, which followed the bytes returned by `read`
+    error: Option<crate::io::Error>,
+    /// Copies of misaligned values for `peek_as`
+    #[cfg(feature = "bytemuck")]
+    aligned: Vec<cast::Aligned>,
+    ioread: Read,
+}
+
+/// Data of an in-memory inner reader, see [`PeekRead::from_slice`]
+///
+/// The data is borrowed by the inner reader, so it lives as long as the
+/// PeekRead. The inner reader isn't read and `pos` is an offset into the data.
+#[derive(Clone, Copy)]
+struct Memory(core::ptr::NonNull<[u8]>);
+
+// Safety: Only used like the `&[u8]` it was created from
+unsafe impl Send for Memory {}
+unsafe impl Sync for Memory {}
+
+impl Memory {
+    /// Safety: `data` must be borrowed by the inner reader of the PeekRead
+    #[inline]
+    unsafe fn new(data: &[u8]) -> Self {
+        return Self(core::ptr::NonNull::from(data));
+    }
+}
+
+/// Minimum number of bytes read from the inner reader on a buffer miss
+struct ChunkSize {
+    size: usize,
+    min: usize,
+    max: usize,
+}
+
+impl ChunkSize {
+    /// Adapts the chunk size to the number of bytes the inner reader returned
+    #[inline]
+    fn observe(&mut self, consumed_reader: usize) {
+        if self.min == self.max {
+            return;
+        }
+
+        if consumed_reader >= self.size {
+            // The inner reader had more to give
+            self.size = (self.size * 2).min(self.max);
+        } else if consumed_reader < self.size / 2 {
+            self.size = (self.size / 2).max(self.min);
+        }
+    }
+}
+
+/// Reads from `read`, retries if the read was interrupted
+#[inline]
+fn read_retry<Read: crate::io::Read + ?Sized>(
+    read: &mut Read,
+    buf: &mut [u8],
+) -> crate::io::Result<usize> {
+    loop {
+        match read.read(buf) {
+            Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => {}
+            result => return result,
+        }
+    }
 }
 
-impl<Read: std::io::Read> PeekRead<Read> {
+/// Reads vectored from `read`, retries if the read was interrupted
+#[inline]
+#[cfg(feature = "std")]
+fn read_vectored_retry<Read: crate::io::Read + ?Sized>(
+    read: &mut Read,
+    bufs: &mut [std::io::IoSliceMut<'_>],
+) -> crate::io::Result<usize> {
+    loop {
+        match read.read_vectored(bufs) {
+            Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => {}
+            result => return result,
+        }
+    }
+}
+
+/// Copies `src` into `bufs` (in order), returns the number of copied bytes
+#[cfg(feature = "std")]
+fn scatter(mut src: &[u8], bufs: &mut [std::io::IoSliceMut<'_>]) -> usize {
+    let mut copied = 0;
+    for buf in bufs {
+        if src.is_empty() {
+            break;
+        }
+
+        let len = buf.len().min(src.len());
+        buf[..len].copy_from_slice(&src[..len]);
+        src = &src[len..];
+        copied += len;
+    }
+
+    return copied;
+}
+
+/// Appends the first `len` bytes of `bufs` to `dst`
+#[cfg(feature = "std")]
+fn gather(dst: &mut Buffer, bufs: &[std::io::IoSliceMut<'_>], mut len: usize) {
+    for buf in bufs {
+        if len == 0 {
+            break;
+        }
+
+        let consumed = buf.len().min(len);
+        dst.extend_from_slice(&buf[..consumed]);
+        len -= consumed;
+    }
+}
+
+/// Returns the parts of `bufs` after the first `skip` bytes
+#[cfg(feature = "std")]
+fn skip_slices<'a>(
+    bufs: &'a mut [std::io::IoSliceMut<'_>],
+    mut skip: usize,
+) -> Vec<std::io::IoSliceMut<'a>> {
+    let mut result = Vec::with_capacity(bufs.len());
+    for buf in bufs {
+        if skip >= buf.len() {
+            skip -= buf.len();
+        } else {
+            result.push(std::io::IoSliceMut::new(&mut buf[skip..]));
+            skip = 0;
+        }
+    }
+
+    return result;
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Returns the buffered bytes (all bytes for in-memory data)
+    #[inline]
+    fn data(&self) -> &[u8] {
+        return match self.memory {
+            // Safety: The data lives as long as the inner reader
+            Some(memory) => unsafe { memory.0.as_ref() },
+            None => &self.buffer,
+        };
+    }
+
     #[inline]
     fn consume_buffer_bytes(&mut self, bytes: usize) {
         if let Some(pos) = self.pos {
-            if bytes >= self.buffer.len() {
-                if self.is_checkpoint_empty() {
+            let pos = pos + bytes;
+            if self.is_checkpoint_empty() && self.memory.is_none() {
+                if pos >= self.buffer.len() {
+                    self.discarded += self.buffer.len() as u64;
                     self.pos = None;
                     self.buffer.clear();
-                } else {
-                    self.pos = Some(0);
+                    return;
                 }
-            } else {
-                // Magic size (where to empty data)
-                if pos >= 128 && self.is_checkpoint_empty() {
-                    self.buffer.drain(..bytes);
+
+                // Magic size (where to empty data), only if the move is cheap
+                if pos >= 128 && pos >= self.buffer.len() - pos {
+                    self.discarded += pos as u64;
+                    discard_front(&mut self.buffer, pos);
                     self.pos = Some(0);
-                } else {
-                    self.pos = Some(pos + bytes);
+                    return;
                 }
             }
+
+            self.pos = Some(pos);
         }
     }
 
-    #[cfg(feature = "checkpoint")]
+    /// Reads from the inner reader until at least `len` bytes are buffered
+    ///
+    /// Bytes read before an error occurred stay buffered.
+    fn fill_buffer(&mut self, len: usize) -> crate::io::Result<()> {
+        let pos = self.pos.unwrap_or(0);
+        while self.data().len() - pos < len {
+            let missing = pos + len - self.data().len();
+            if self.read_chunk(missing)? == 0 {
+                return Err(crate::io::Error::new(
+                    crate::io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
+            }
+
+            self.pos = Some(pos);
+        }
+
+        return Ok(());
+    }
+
+    /// Appends the next chunk of the inner reader to the buffer
+    ///
+    /// The chunk is at least `len` bytes big (if the inner reader has enough
+    /// bytes available).
     #[inline]
-    fn is_checkpoint_empty(&self) -> bool {
-        return self.checkpoints.is_empty();
+    fn read_chunk(&mut self, len: usize) -> crate::io::Result<usize> {
+        let consumed_reader = self.read_ahead(len.max(self.chunk.size))?;
+        self.chunk.observe(consumed_reader);
+        return Ok(consumed_reader);
+    }
+
+    /// Appends at most `len` bytes of the inner reader to the buffer
+    #[cfg(not(feature = "read_buf"))]
+    fn read_ahead(&mut self, len: usize) -> crate::io::Result<usize> {
+        self.pending_error()?;
+        if self.memory.is_some() {
+            // All data is available already
+            return Ok(0);
+        }
+
+        let start = self.buffer.len();
+        self.buffer.resize(start + len, 0);
+        match read_retry(&mut self.ioread, &mut self.buffer[start..]) {
+            Ok(consumed_reader) => {
+                self.buffer.truncate(start + consumed_reader);
+                return Ok(consumed_reader);
+            }
+            Err(err) => {
+                self.buffer.truncate(start);
+                return Err(err);
+            }
+        }
+    }
+
+    /// Appends at most `len` bytes of the inner reader to the buffer
+    ///
+    /// Reads into the spare capacity of the buffer, so it doesn't have to be
+    /// initialized first.
+    #[cfg(feature = "read_buf")]
+    fn read_ahead(&mut self, len: usize) -> crate::io::Result<usize> {
+        self.pending_error()?;
+        if self.memory.is_some() {
+            // All data is available already
+            return Ok(0);
+        }
+
+        self.buffer.reserve(len);
+        let mut spare: std::io::BorrowedBuf<'_> =
+            (&mut self.buffer.spare_capacity_mut()[..len]).into();
+        loop {
+            match self.ioread.read_buf(spare.unfilled()) {
+                Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+                Ok(()) => break,
+            }
+        }
+
+        let consumed_reader = spare.len();
+        // Safety: The inner reader initialized and filled these bytes
+        unsafe {
+            self.buffer.set_len(self.buffer.len() + consumed_reader);
+        }
+
+        return Ok(consumed_reader);
+    }
+
+    /// Keeps the bytes of a failed `read_exact` buffered, so nothing is consumed
+    #[cold]
+    fn unread_exact(&mut self, pos: usize, read: &[u8], err: crate::io::Error) -> crate::io::Error {
+        if !read.is_empty() {
+            self.buffer.extend_from_slice(read);
+            self.pos = Some(pos);
+        }
+
+        return err;
     }
 
-    #[cfg(not(feature = "checkpoint"))]
+    /// Copies the inner reader straight to `writer`, once the buffer is drained
+    ///
+    /// Only the bytes `writer` didn't take are buffered if it fails.
+    #[cfg(feature = "std")]
+    fn copy_inner_to<W: std::io::Write + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> crate::io::Result<u64> {
+        self.consume_buffer_bytes(0);
+        self.pending_error()?;
+
+        let mut chunk = [0; COPY_SIZE];
+        let mut copied = 0;
+        loop {
+            let len = read_retry(&mut self.ioread, &mut chunk)?;
+            if len == 0 {
+                return Ok(copied);
+            }
+
+            let mut written = 0;
+            while written < len {
+                let err = match writer.write(&chunk[written..len]) {
+                    Ok(0) => crate::io::Error::new(
+                        crate::io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ),
+                    Ok(consumed) => {
+                        written += consumed;
+                        continue;
+                    }
+                    Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => continue,
+                    Err(err) => err,
+                };
+
+                self.discarded += written as u64;
+                self.buffer.extend_from_slice(&chunk[written..len]);
+                self.pos = Some(0);
+                return Err(err);
+            }
+
+            self.discarded += len as u64;
+            copied += len as u64;
+        }
+    }
+
+    /// Returns the error, which was deferred by [`PeekRead::defer_error`]
     #[inline]
-    fn is_checkpoint_empty(&self) -> bool {
-        return true;
+    fn pending_error(&mut self) -> crate::io::Result<()> {
+        return match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        };
     }
 
-    pub fn new(read: Read) -> Self {
-        Self {
-            ioread: read,
-            buffer: Vec::new(),
-            pos: None,
-            #[cfg(feature = "checkpoint")]
-            checkpoints: Vec::new(),
+    /// Returns `err`, or `consumed` if some bytes were already consumed
+    ///
+    /// The consumed bytes must be returned, so `err` is returned by the next
+    /// read of the inner reader instead.
+    #[cold]
+    fn defer_error(&mut self, consumed: usize, err: crate::io::Error) -> crate::io::Result<usize> {
+        if consumed == 0 {
+            return Err(err);
         }
+
+        self.error = Some(err);
+        return Ok(consumed);
+    }
+
+    /// Compiled to `true` for [`NoCheckpoints`]
+    #[inline]
+    fn is_checkpoint_empty(&self) -> bool {
+        return !Mode::ENABLED || self.checkpoints.is_empty();
+    }
+
+    /// Returns the number of consumed bytes
+    ///
+    /// Counts from the creation of the PeekRead. Rolling back a checkpoint
+    /// also rolls back the position.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        return self.discarded + self.pos.unwrap_or(0) as u64;
     }
 
     /// Peeks the stream
     ///
     /// Returns the top of the stream without consuming its contents
-    #[must_use]
-    pub fn peek(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        if let Some(pos) = self.pos {
-            let consumed = buf.len().min(self.buffer.len() - pos);
-            if consumed != 0 {
-                buf[..consumed].copy_from_slice(&self.buffer[pos..(consumed + pos)]);
-            }
+    pub fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = buf.len().min(self.data().len() - pos);
+        if consumed != 0 {
+            buf[..consumed].copy_from_slice(&self.data()[pos..(consumed + pos)]);
+        }
 
-            if consumed == buf.len() {
-                return Ok(consumed);
-            } else {
-                let consumed_reader = self.ioread.read(&mut buf[consumed..])?;
-                self.buffer
-                    .extend_from_slice(&buf[consumed..consumed + consumed_reader]);
-                return Ok(consumed + consumed_reader);
-            }
-        } else {
-            let result = self.ioread.read(buf)?;
-            self.buffer.extend_from_slice(&buf[..result]);
-            self.pos = Some(0);
-            return Ok(result);
+        if consumed == buf.len() || self.memory.is_some() {
+            return Ok(consumed);
         }
+
+        // Errors are returned even if some bytes are buffered, they stay buffered
+        if buf.len() - consumed < self.chunk.size {
+            self.read_chunk(0)?;
+            let consumed_reader = (buf.len() - consumed).min(self.data().len() - pos - consumed);
+            buf[consumed..consumed + consumed_reader]
+                .copy_from_slice(&self.data()[pos + consumed..pos + consumed + consumed_reader]);
+            self.pos = Some(pos);
+            return Ok(consumed + consumed_reader);
+        }
+
+        self.pending_error()?;
+        let consumed_reader = read_retry(&mut self.ioread, &mut buf[consumed..])?;
+        self.buffer
+            .extend_from_slice(&buf[consumed..consumed + consumed_reader]);
+        self.pos = Some(pos);
+        return Ok(consumed + consumed_reader);
     }
 
     /// Peeks the stream
     ///
     /// Returns the top of the stream without consuming its contents
-    #[must_use]
-    pub fn peek_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
-        if let Some(pos) = self.pos {
-            let consumed = buf.len().min(self.buffer.len() - pos);
-            if consumed != 0 {
-                buf[..consumed].copy_from_slice(&self.buffer[pos..(consumed + pos)]);
+    pub fn peek_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        self.fill_buffer(buf.len())?;
+
+        let pos = self.pos.unwrap_or(0);
+        buf.copy_from_slice(&self.data()[pos..(pos + buf.len())]);
+        return Ok(());
+    }
+
+    /// Peeks exactly `len` bytes of the stream
+    ///
+    /// Like [`PeekRead::peek_exact`], but borrows the bytes from the buffer.
+    pub fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]> {
+        self.fill_buffer(len)?;
+
+        let pos = self.pos.unwrap_or(0);
+        return Ok(&self.data()[pos..(pos + len)]);
+    }
+
+    /// Peeks the stream into multiple buffers
+    ///
+    /// Like [`PeekRead::peek`], but fills `bufs` in order. Bytes which aren't
+    /// buffered yet are read with the `read_vectored` of the inner reader.
+    #[cfg(feature = "std")]
+    pub fn peek_vectored(
+        &mut self,
+        bufs: &mut [std::io::IoSliceMut<'_>],
+    ) -> crate::io::Result<usize> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = scatter(&self.data()[pos..], bufs);
+        let total: usize = bufs.iter().map(|buf| buf.len()).sum();
+        if consumed == total || self.memory.is_some() {
+            return Ok(consumed);
+        }
+
+        self.pending_error()?;
+        let mut rest = skip_slices(bufs, consumed);
+        let consumed_reader = read_vectored_retry(&mut self.ioread, &mut rest)?;
+        gather(&mut self.buffer, &rest, consumed_reader);
+        self.pos = Some(pos);
+        return Ok(consumed + consumed_reader);
+    }
+
+    /// Peeks the stream into a possibly uninitialized buffer
+    ///
+    /// Like [`PeekRead::peek`], but doesn't require `cursor` to be initialized.
+    #[cfg(feature = "read_buf")]
+    pub fn peek_buf(&mut self, mut cursor: std::io::BorrowedCursor<'_>) -> crate::io::Result<()> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = cursor.capacity().min(self.data().len() - pos);
+        cursor.append(&self.data()[pos..(pos + consumed)]);
+        if cursor.capacity() == 0 {
+            return Ok(());
+        }
+
+        let consumed_reader = self.read_ahead(cursor.capacity())?;
+        let start = self.buffer.len() - consumed_reader;
+        cursor.append(&self.buffer[start..]);
+        self.pos = Some(pos);
+        return Ok(());
+    }
+
+    /// Peeks exactly `len` bytes of the stream without blocking
+    ///
+    /// Returns `None` if the inner reader would block before `len` bytes are
+    /// available. The bytes read so far stay buffered, so the call can be
+    /// repeated once the inner reader is ready again.
+    pub fn try_peek_exact(&mut self, len: usize) -> crate::io::Result<Option<&[u8]>> {
+        match self.fill_buffer(len) {
+            Ok(()) => {}
+            Err(err) if err.kind() == crate::io::ErrorKind::WouldBlock => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        let pos = self.pos.unwrap_or(0);
+        return Ok(Some(&self.data()[pos..(pos + len)]));
+    }
+
+    /// Writes the rest of the stream to `writer`
+    ///
+    /// Writes the buffered bytes first and then copies the inner reader in
+    /// chunks, without buffering them (unless a checkpoint is active).
+    /// Returns the number of copied bytes. If `writer` fails, exactly the
+    /// written bytes are consumed, the rest stays buffered.
+    #[cfg(feature = "std")]
+    pub fn copy_to<W: std::io::Write + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> crate::io::Result<u64> {
+        let mut copied = 0;
+        loop {
+            let pos = self.pos.unwrap_or(0);
+            if pos >= self.data().len() {
+                if self.is_checkpoint_empty() && self.memory.is_none() {
+                    return Ok(copied + self.copy_inner_to(writer)?);
+                }
+
+                if self.read_chunk(COPY_SIZE)? == 0 {
+                    return Ok(copied);
+                }
+
+                self.pos = Some(pos);
             }
 
-            if consumed == buf.len() {
-                return Ok(());
-            } else {
-                self.ioread.read_exact(&mut buf[consumed..])?;
-                self.buffer.extend_from_slice(&buf[consumed..]);
-                return Ok(());
+            match writer.write(&self.data()[pos..]) {
+                Ok(0) => {
+                    return Err(crate::io::Error::new(
+                        crate::io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ));
+                }
+                Ok(written) => {
+                    self.consume_buffer_bytes(written);
+                    copied += written as u64;
+                }
+                Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
             }
-        } else {
-            self.ioread.read_exact(buf)?;
-            self.buffer.extend_from_slice(&buf);
-            self.pos = Some(0);
-            return Ok(());
         }
     }
+}
 
+impl<Read: crate::io::Read, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    fn with_mode(read: Read) -> Self {
+        Self {
+            ioread: read,
+            buffer: Buffer::new(),
+            pos: None,
+            memory: None,
+            chunk: ChunkSize {
+                size: 0,
+                min: 0,
+                max: 0,
+            },
+            checkpoints: Vec::new(),
+            mode: core::marker::PhantomData,
+            discarded: 0,
+            error: None,
+            #[cfg(feature = "bytemuck")]
+            aligned: Vec::new(),
+        }
+    }
+
+    /// Reads at least `size` bytes from the inner reader on a buffer miss
+    ///
+    /// Useful for unbuffered inner readers (like `File` or `TcpStream`), where
+    /// every read is a syscall. Reads which are at least `size` bytes big
+    /// bypass the buffer.
+    pub fn with_read_ahead(mut self, size: usize) -> Self {
+        self.chunk = ChunkSize {
+            size,
+            min: size,
+            max: size,
+        };
+        return self;
+    }
+
+    /// Like [`PeekRead::with_read_ahead`], but adapts the read-ahead size
+    ///
+    /// Starts with `min` bytes and doubles the size (up to `max`) as long as
+    /// the inner reader fills the whole chunk. Halves the size again if the
+    /// inner reader returns less than half of it. `min` must not be zero (it
+    /// couldn't be doubled), use `with_read_ahead(0)` to disable read-ahead.
+    pub fn with_adaptive_read_ahead(mut self, min: usize, max: usize) -> Self {
+        assert!(min > 0, "min must not be zero");
+        assert!(min <= max, "min must be less than or equal to max");
+        self.chunk = ChunkSize {
+            size: min,
+            min,
+            max,
+        };
+        return self;
+    }
+}
+
+impl<Read: crate::io::Read> PeekRead<Read, Checkpoints> {
+    /// Creates a PeekRead, which buffers the peeked bytes of `read`
+    ///
+    /// Use [`PeekRead::from_slice`] for in-memory data.
+    pub fn new(read: Read) -> Self {
+        return Self::with_mode(read);
+    }
+}
+
+impl<'a> PeekRead<&'a [u8], Checkpoints> {
+    /// Creates a PeekRead for in-memory data
+    ///
+    /// Nothing is copied into the buffer: peeks borrow from `data` and
+    /// checkpoints only store offsets.
+    pub fn from_slice(data: &'a [u8]) -> Self {
+        let mut read = Self::with_mode(data);
+        // Safety: `data` is the inner reader
+        read.memory = Some(unsafe { Memory::new(data) });
+        read.pos = Some(0);
+        return read;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> PeekRead<std::io::Cursor<&'a [u8]>, Checkpoints> {
+    /// Like [`PeekRead::from_slice`], starts at the current position of
+    /// `cursor`
+    pub fn from_cursor(cursor: std::io::Cursor<&'a [u8]>) -> Self {
+        let data: &'a [u8] = cursor.get_ref();
+        let start = usize::try_from(cursor.position())
+            .unwrap_or(usize::MAX)
+            .min(data.len());
+        let mut read = Self::with_mode(cursor);
+        // Safety: The data is borrowed by the cursor
+        read.memory = Some(unsafe { Memory::new(&data[start..]) });
+        read.pos = Some(0);
+        return read;
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized> PeekRead<Read, Checkpoints> {
     /// Creates a checkpoint and calls fn_checkpoint afterwards
     ///
     /// Resets the reader to the current state if an Error is returned. Can be stacked.
-    #[cfg(feature = "checkpoint")]
-    #[must_use]
     pub fn checkpoint<T, E, F: FnOnce(&mut Self) -> Result<T, E>>(
         &mut self,
         fn_checkpoint: F,
     ) -> Result<T, E> {
-        self.checkpoints.push(self.pos.unwrap_or(0));
+        self.push_checkpoint();
 
         let result = fn_checkpoint(self);
-        return match result {
-            Ok(result) => {
-                self.checkpoints.pop();
-                Ok(result)
+        self.pop_checkpoint(result.is_err());
+        return result;
+    }
+
+    /// Remembers the current position until [`PeekRead::pop_checkpoint`]
+    pub(crate) fn push_checkpoint(&mut self) {
+        self.checkpoints.push(self.pos.unwrap_or(0));
+    }
+
+    /// Drops the latest checkpoint, going back to it if `reset` is set
+    pub(crate) fn pop_checkpoint(&mut self, reset: bool) {
+        let pos = self.checkpoints.pop().unwrap();
+        if reset {
+            self.pos = Some(pos);
+        }
+    }
+}
+
+impl<Read: crate::io::Read> PeekRead<Read, NoCheckpoints> {
+    /// Creates a PeekRead without checkpoint support
+    ///
+    /// Saves the checkpoint bookkeeping on every read.
+    pub fn without_checkpoints(read: Read) -> Self {
+        return Self::with_mode(read);
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> crate::io::Read
+    for PeekRead<Read, Mode>
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = buf.len().min(self.data().len() - pos);
+        if consumed != 0 {
+            buf[..consumed].copy_from_slice(&self.data()[pos..(consumed + pos)]);
+            self.consume_buffer_bytes(consumed);
+        }
+
+        if consumed == buf.len() || self.memory.is_some() {
+            // The consumed bytes were all buffered already
+            return Ok(consumed);
+        }
+
+        if buf.len() - consumed < self.chunk.size {
+            // Small reads are served from the buffer
+            let pos = self.pos.unwrap_or(0);
+            match self.read_chunk(0) {
+                Ok(_) => {
+                    let consumed_reader = (buf.len() - consumed).min(self.data().len() - pos);
+                    buf[consumed..consumed + consumed_reader]
+                        .copy_from_slice(&self.data()[pos..pos + consumed_reader]);
+                    self.pos = Some(pos);
+                    self.consume_buffer_bytes(consumed_reader);
+                    return Ok(consumed + consumed_reader);
+                }
+                Err(err) => return self.defer_error(consumed, err),
             }
-            Err(err) => {
-                self.pos = Some(self.checkpoints.pop().unwrap());
-                Err(err)
+        }
+
+        // The consumed bytes were only partially buffered
+        let result = self
+            .pending_error()
+            .and_then(|()| read_retry(&mut self.ioread, &mut buf[consumed..]));
+        match result {
+            Ok(consumed_reader) => {
+                if !self.is_checkpoint_empty() {
+                    self.buffer
+                        .extend_from_slice(&buf[consumed..consumed + consumed_reader]);
+                    self.pos = Some(self.buffer.len());
+                } else {
+                    self.discarded += consumed_reader as u64;
+                }
+                return Ok(consumed + consumed_reader);
             }
-        };
+            Err(err) => return self.defer_error(consumed, err),
+        }
     }
 
-    fn read_with_pos(&mut self, buf: &mut [u8], pos: usize) -> std::io::Result<usize> {
-        let consumed = buf.len().min(self.buffer.len() - pos);
+    #[cfg(feature = "std")]
+    fn read_vectored(&mut self, bufs: &mut [std::io::IoSliceMut<'_>]) -> crate::io::Result<usize> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = scatter(&self.data()[pos..], bufs);
         if consumed != 0 {
-            buf[..consumed].copy_from_slice(&self.buffer[pos..(consumed + pos)]);
             self.consume_buffer_bytes(consumed);
         }
 
-        if consumed == buf.len() {
+        let total: usize = bufs.iter().map(|buf| buf.len()).sum();
+        if consumed == total || self.memory.is_some() {
             // The consumed bytes were all buffered already
             return Ok(consumed);
-        } else {
-            // The consumed bytes were only partially buffered
-            let consumed_reader = self.ioread.read(&mut buf[consumed..])?;
-            if !self.is_checkpoint_empty() {
-                self.buffer.extend_from_slice(&buf[consumed..]);
-                self.pos = Some(self.buffer.len());
+        }
+
+        // The consumed bytes were only partially buffered
+        let result = self.pending_error().and_then(|()| {
+            if consumed == 0 {
+                read_vectored_retry(&mut self.ioread, bufs)
+            } else {
+                read_vectored_retry(&mut self.ioread, &mut skip_slices(bufs, consumed))
             }
-            return Ok(consumed + consumed_reader);
+        });
+
+        match result {
+            Ok(consumed_reader) => {
+                if !self.is_checkpoint_empty() {
+                    gather(
+                        &mut self.buffer,
+                        &skip_slices(bufs, consumed),
+                        consumed_reader,
+                    );
+                    self.pos = Some(self.buffer.len());
+                } else {
+                    self.discarded += consumed_reader as u64;
+                }
+                return Ok(consumed + consumed_reader);
+            }
+            Err(err) => return self.defer_error(consumed, err),
         }
     }
 
-    fn read_exact_with_pos(&mut self, buf: &mut [u8], pos: usize) -> std::io::Result<()> {
-        let consumed = buf.len().min(self.buffer.len() - pos);
+    #[cfg(feature = "std")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> crate::io::Result<usize> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = self.data().len() - pos;
         if consumed != 0 {
-            buf[..consumed].copy_from_slice(&self.buffer[pos..(consumed + pos)]);
+            buf.extend_from_slice(&self.data()[pos..]);
             self.consume_buffer_bytes(consumed);
         }
 
-        if consumed == buf.len() {
+        if self.memory.is_some() {
+            return Ok(consumed);
+        }
+
+        let start = buf.len();
+        let result = self
+            .pending_error()
+            .and_then(|()| self.ioread.read_to_end(buf));
+        if !self.is_checkpoint_empty() {
+            self.buffer.extend_from_slice(&buf[start..]);
+            self.pos = Some(self.buffer.len());
+        } else {
+            self.discarded += (buf.len() - start) as u64;
+        }
+
+        return Ok(consumed + result?);
+    }
+
+    #[cfg(feature = "std")]
+    fn read_to_string(&mut self, buf: &mut String) -> crate::io::Result<usize> {
+        let mut bytes = core::mem::take(buf).into_bytes();
+        let start = bytes.len();
+        let result = self.read_to_end(&mut bytes);
+        let result = if core::str::from_utf8(&bytes[start..]).is_ok() {
+            result
+        } else {
+            bytes.truncate(start);
+            result.and_then(|_| {
+                Err(crate::io::Error::new(
+                    crate::io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ))
+            })
+        };
+
+        // Safety: The bytes were valid UTF-8 before and only valid UTF-8 was appended
+        *buf = unsafe { String::from_utf8_unchecked(bytes) };
+        return result;
+    }
+
+    #[cfg(feature = "read_buf")]
+    fn read_buf(&mut self, mut cursor: std::io::BorrowedCursor<'_>) -> crate::io::Result<()> {
+        let pos = self.pos.unwrap_or(0);
+        let consumed = cursor.capacity().min(self.data().len() - pos);
+        if consumed != 0 {
+            cursor.append(&self.data()[pos..(pos + consumed)]);
+            self.consume_buffer_bytes(consumed);
+        }
+
+        if cursor.capacity() == 0 || self.memory.is_some() {
             // The consumed bytes were all buffered already
             return Ok(());
+        }
+
+        // The consumed bytes were only partially buffered
+        let result = if self.is_checkpoint_empty() {
+            let written = cursor.written();
+            let result = self.pending_error().and_then(|()| loop {
+                match self.ioread.read_buf(cursor.reborrow()) {
+                    Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => {}
+                    result => break result,
+                }
+            });
+            self.discarded += (cursor.written() - written) as u64;
+            result
         } else {
-            // The consumed bytes were only partially buffered
-            self.ioread.read_exact(&mut buf[consumed..])?;
-            if !self.is_checkpoint_empty() {
-                self.buffer.extend_from_slice(&buf[consumed..]);
+            // Must be buffered anyways, so read ahead and copy
+            let capacity = cursor.capacity();
+            self.read_ahead(capacity).map(|consumed_reader| {
+                let start = self.buffer.len() - consumed_reader;
+                cursor.append(&self.buffer[start..]);
                 self.pos = Some(self.buffer.len());
-            }
-            return Ok(());
+            })
+        };
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(err) => return self.defer_error(consumed, err).map(|_| ()),
         }
     }
-}
 
-impl<Read: std::io::Read> std::io::Read for PeekRead<Read> {
-    #[inline]
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        if let Some(pos) = self.pos {
-            return self.read_with_pos(buf, pos);
-        } else {
-            let result = self.ioread.read(buf)?;
-            if !self.is_checkpoint_empty() {
-                self.buffer.extend_from_slice(&buf[..result]);
-                self.pos = Some(result);
+    /// Like `read_exact`, but the bytes read before an error occurred are
+    /// appended to `cursor` (instead of being kept buffered).
+    #[cfg(feature = "read_buf")]
+    fn read_buf_exact(&mut self, mut cursor: std::io::BorrowedCursor<'_>) -> crate::io::Result<()> {
+        while cursor.capacity() > 0 {
+            let written = cursor.written();
+            self.read_buf(cursor.reborrow())?;
+            if cursor.written() == written {
+                return Err(crate::io::Error::new(
+                    crate::io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
             }
-            return Ok(result);
         }
+
+        return Ok(());
     }
 
     #[inline]
-    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
-        if let Some(pos) = self.pos {
-            return self.read_exact_with_pos(buf, pos);
-        } else {
-            self.ioread.read_exact(buf)?;
-            if !self.is_checkpoint_empty() {
-                self.buffer.extend_from_slice(&buf);
-                self.pos = Some(self.buffer.len());
-            }
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        if buf.len() < self.chunk.size || self.memory.is_some() {
+            // Small reads (and in-memory data) are served from the buffer
+            self.fill_buffer(buf.len())?;
+            let pos = self.pos.unwrap_or(0);
+            buf.copy_from_slice(&self.data()[pos..(pos + buf.len())]);
+            self.consume_buffer_bytes(buf.len());
+            return Ok(());
+        }
+
+        let pos = self.pos.unwrap_or(0);
+        let consumed = buf.len().min(self.data().len() - pos);
+        if consumed != 0 {
+            buf[..consumed].copy_from_slice(&self.data()[pos..(consumed + pos)]);
+        }
+
+        if consumed == buf.len() {
+            // The consumed bytes were all buffered already
+            self.consume_buffer_bytes(consumed);
             return Ok(());
         }
+
+        // The consumed bytes were only partially buffered
+        self.pending_error()?;
+        let mut filled = consumed;
+        while filled < buf.len() {
+            match read_retry(&mut self.ioread, &mut buf[filled..]) {
+                Ok(0) => {
+                    return Err(self.unread_exact(
+                        pos,
+                        &buf[consumed..filled],
+                        crate::io::Error::new(
+                            crate::io::ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        ),
+                    ));
+                }
+                Ok(consumed_reader) => filled += consumed_reader,
+                Err(err) => return Err(self.unread_exact(pos, &buf[consumed..filled], err)),
+            }
+        }
+
+        self.consume_buffer_bytes(consumed);
+        if !self.is_checkpoint_empty() {
+            self.buffer.extend_from_slice(&buf[consumed..]);
+            self.pos = Some(self.buffer.len());
+        } else {
+            self.discarded += (buf.len() - consumed) as u64;
+        }
+        return Ok(());
     }
 }
 
-impl<Read: std::io::BufRead> std::io::BufRead for PeekRead<Read> {
-    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+#[cfg(feature = "std")]
+impl<Read: std::io::BufRead + ?Sized, Mode: CheckpointMode> std::io::BufRead
+    for PeekRead<Read, Mode>
+{
+    fn fill_buf(&mut self) -> crate::io::Result<&[u8]> {
         if let Some(pos) = self.pos {
-            if self.buffer.len() > pos {
-                return Ok(&self.buffer[pos..]);
+            if self.data().len() > pos || self.memory.is_some() {
+                return Ok(&self.data()[pos..]);
             } else {
+                self.pending_error()?;
                 return self.ioread.fill_buf();
             }
         } else {
+            self.pending_error()?;
             return self.ioread.fill_buf();
         }
     }
 
     fn consume(&mut self, amt: usize) {
         if let Some(pos) = self.pos {
-            if amt > self.buffer.len() - pos {
-                self.consume_buffer_bytes(self.buffer.len() - pos);
-                self.ioread.consume(amt - (self.buffer.len() - pos));
+            let buffered = self.data().len() - pos;
+            if amt > buffered && self.memory.is_none() {
+                self.consume_buffer_bytes(buffered);
+                self.ioread.consume(amt - buffered);
+                self.discarded += (amt - buffered) as u64;
             } else {
-                self.consume_buffer_bytes(amt);
+                self.consume_buffer_bytes(amt.min(buffered));
             }
         } else {
             self.ioread.consume(amt);
+            self.discarded += amt as u64;
         }
     }
 }
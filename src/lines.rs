@@ -0,0 +1,137 @@
+use crate::search::Scan;
+use crate::{CheckpointMode, PeekRead};
+use alloc::vec::Vec;
+
+/// Error of [`PeekRead::peek_line`]
+#[derive(Debug)]
+pub enum LineError {
+    /// The inner reader failed
+    Io(crate::io::Error),
+    /// The line (including its terminator) is longer than `max_len` bytes
+    TooLong { max_len: usize },
+}
+
+impl core::fmt::Display for LineError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return match self {
+            LineError::Io(err) => err.fmt(f),
+            LineError::TooLong { max_len } => {
+                write!(f, "line exceeds the maximum length of {} bytes", max_len)
+            }
+        };
+    }
+}
+
+impl core::error::Error for LineError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        return match self {
+            LineError::Io(err) => Some(err),
+            LineError::TooLong { .. } => None,
+        };
+    }
+}
+
+impl From<crate::io::Error> for LineError {
+    fn from(err: crate::io::Error) -> Self {
+        return LineError::Io(err);
+    }
+}
+
+impl From<LineError> for crate::io::Error {
+    fn from(err: LineError) -> Self {
+        return match err {
+            LineError::Io(err) => err,
+            #[cfg(feature = "std")]
+            err @ LineError::TooLong { .. } => {
+                crate::io::Error::new(crate::io::ErrorKind::InvalidData, err)
+            }
+            #[cfg(not(feature = "std"))]
+            LineError::TooLong { .. } => crate::io::Error::new(
+                crate::io::ErrorKind::InvalidData,
+                "line exceeds the maximum length",
+            ),
+        };
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Peeks the next line, including its terminator (`\n` or `\r\n`)
+    ///
+    /// The last line of the stream may have no terminator, at the end of the
+    /// stream the result is empty. Fails with [`LineError::TooLong`] instead
+    /// of buffering more than `max_len` bytes (for the check, at most one
+    /// byte more is read). Doesn't consume anything.
+    pub fn peek_line(&mut self, max_len: usize) -> Result<&[u8], LineError> {
+        let len = match self.scan_for(b'\n', max_len)? {
+            Scan::Found(offset) => offset + 1,
+            Scan::Eof => self.data().len() - self.pos.unwrap_or(0),
+            Scan::Limit => {
+                // The last line may be exactly `max_len` bytes long
+                let buffered = self.data().len() - self.pos.unwrap_or(0);
+                if buffered > max_len || self.scan_more(buffered, max_len.saturating_add(1))? {
+                    return Err(LineError::TooLong { max_len });
+                }
+
+                buffered
+            }
+        };
+
+        let pos = self.pos.unwrap_or(0);
+        return Ok(&self.data()[pos..(pos + len)]);
+    }
+
+    /// Returns an iterator over the lines and their offsets in the stream
+    ///
+    /// Yields every line without its terminator, together with
+    /// [`PeekRead::position`] of its start. Stops after the first error, see
+    /// [`PeekRead::peek_line`].
+    pub fn lines_with_offsets(&mut self, max_len: usize) -> LinesWithOffsets<'_, Read, Mode> {
+        return LinesWithOffsets {
+            read: self,
+            max_len,
+            done: false,
+        };
+    }
+}
+
+/// Iterator of [`PeekRead::lines_with_offsets`]
+pub struct LinesWithOffsets<'a, Read: crate::io::Read + ?Sized, Mode: CheckpointMode> {
+    read: &'a mut PeekRead<Read, Mode>,
+    max_len: usize,
+    done: bool,
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> Iterator
+    for LinesWithOffsets<'_, Read, Mode>
+{
+    type Item = Result<(u64, Vec<u8>), LineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let offset = self.read.position();
+        let line = match self.read.peek_line(self.max_len) {
+            Ok([]) => {
+                self.done = true;
+                return None;
+            }
+            Ok(line) => line,
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+
+        let len = line.len();
+        let mut content = line;
+        if let Some(rest) = content.strip_suffix(b"\n") {
+            content = rest.strip_suffix(b"\r").unwrap_or(rest);
+        }
+
+        let content = content.to_vec();
+        self.read.consume_buffer_bytes(len);
+        return Some(Ok((offset, content)));
+    }
+}
@@ -0,0 +1,32 @@
+/// Selects the checkpoint support of a [`crate::PeekRead`] at compile time
+///
+/// Unlike a cargo feature, the choice is made per instance, so one dependency
+/// requiring checkpoints doesn't slow down the PeekReads of everyone else.
+pub trait CheckpointMode: sealed::Sealed {
+    /// Whether reads have to be recorded for checkpoints
+    const ENABLED: bool;
+}
+
+/// PeekRead with support for checkpoints (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checkpoints;
+
+/// PeekRead without support for checkpoints, created with
+/// [`crate::PeekRead::without_checkpoints`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoCheckpoints;
+
+impl CheckpointMode for Checkpoints {
+    const ENABLED: bool = true;
+}
+
+impl CheckpointMode for NoCheckpoints {
+    const ENABLED: bool = false;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Checkpoints {}
+    impl Sealed for super::NoCheckpoints {}
+}
@@ -0,0 +1,74 @@
+use crate::{CheckpointMode, PeekRead};
+use aho_corasick::automaton::Automaton;
+
+/// Patterns for [`PeekRead::find_any`]
+///
+/// Compiled into an Aho-Corasick DFA once, so it can be reused for every
+/// search.
+#[derive(Debug, Clone)]
+pub struct Patterns {
+    dfa: aho_corasick::dfa::DFA,
+}
+
+impl Patterns {
+    /// Compiles `patterns`, their ids are their indices
+    ///
+    /// If several patterns match at the same offset, the earliest one wins.
+    pub fn new<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+        patterns: I,
+    ) -> Result<Self, aho_corasick::BuildError> {
+        let dfa = aho_corasick::dfa::Builder::new()
+            .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+            .build(patterns)?;
+        return Ok(Self { dfa });
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Finds the leftmost pattern in the lookahead
+    ///
+    /// Returns the id of the pattern and the offset where it starts. If
+    /// several patterns start there, the earliest one (by id) wins. Scans at
+    /// most `max_scan` bytes, reading the inner reader only as far as
+    /// required: past a match only while a preferred pattern could still
+    /// match. Each byte is scanned only once, also across reads of the inner
+    /// reader (so patterns can straddle them). Matches must end within
+    /// `max_scan` bytes. Returns `None` if the stream ended or the limit was
+    /// reached first. Doesn't consume anything.
+    pub fn find_any(
+        &mut self,
+        patterns: &Patterns,
+        max_scan: usize,
+    ) -> crate::io::Result<Option<(usize, usize)>> {
+        let dfa = &patterns.dfa;
+        let mut sid = dfa
+            .start_state(aho_corasick::Anchored::No)
+            .expect("unanchored searches are supported");
+        let mut matched = None;
+        let mut scanned = 0;
+        loop {
+            if dfa.is_dead(sid) {
+                return Ok(matched);
+            }
+
+            if dfa.is_match(sid) {
+                // Tentative, a preferred pattern may still match
+                let pattern = dfa.match_pattern(sid, 0);
+                let offset = scanned - dfa.pattern_len(pattern);
+                matched = Some((pattern.as_usize(), offset));
+            }
+
+            let pos = self.pos.unwrap_or(0);
+            if scanned < self.data().len() - pos && scanned < max_scan {
+                let byte = self.data()[pos + scanned];
+                sid = dfa.next_state(aho_corasick::Anchored::No, sid, byte);
+                scanned += 1;
+                continue;
+            }
+
+            if scanned >= max_scan || !self.scan_more(scanned, max_scan)? {
+                return Ok(matched);
+            }
+        }
+    }
+}
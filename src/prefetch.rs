@@ -0,0 +1,156 @@
+use crate::PeekRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// [`PeekRead`] which reads the inner reader on a background thread
+pub type PrefetchPeekRead = PeekRead<Prefetch>;
+
+/// Reads the inner reader ahead on a background thread
+///
+/// The thread fills a bounded queue of chunks. It stops if the queue reached
+/// its high-water mark and continues as soon as the queue is consumed. Errors
+/// of the inner reader are returned in stream order. The thread stops after
+/// the first error, which is then returned by every later read.
+pub struct Prefetch {
+    receiver: Option<Receiver<std::io::Result<Vec<u8>>>>,
+    chunk: Vec<u8>,
+    pos: usize,
+    /// Kind and message of the error, which stopped the thread
+    failed: Option<(std::io::ErrorKind, String)>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Prefetch {
+    /// Reads chunks of 64 KiB and queues at most 16 of them
+    pub fn new<Read: std::io::Read + Send + 'static>(read: Read) -> Self {
+        return Self::with_high_water_mark(read, 64 * 1024, 16);
+    }
+
+    /// Reads chunks of at most `chunk_size` bytes and queues at most
+    /// `high_water_mark` of them
+    pub fn with_high_water_mark<Read: std::io::Read + Send + 'static>(
+        mut read: Read,
+        chunk_size: usize,
+        high_water_mark: usize,
+    ) -> Self {
+        assert!(chunk_size > 0, "chunk_size must not be zero");
+
+        let (sender, receiver) = sync_channel(high_water_mark);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let mut chunk = vec![0; chunk_size];
+                    match crate::read_retry(&mut read, &mut chunk) {
+                        // End of stream, the receiver notices the disconnect
+                        Ok(0) => return,
+                        Ok(consumed) => {
+                            chunk.truncate(consumed);
+                            if sender.send(Ok(chunk)).is_err() {
+                                // Dropped
+                                return;
+                            }
+                        }
+                        Err(err) => {
+                            // A failed reader isn't read again
+                            let _ = sender.send(Err(err));
+                            return;
+                        }
+                    }
+                }
+            })
+        };
+
+        return Self {
+            receiver: Some(receiver),
+            chunk: Vec::new(),
+            pos: 0,
+            failed: None,
+            stop,
+            thread: Some(thread),
+        };
+    }
+
+    /// Waits for the background thread, returns an error if it panicked
+    fn join(&mut self) -> std::io::Result<()> {
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                return Err(std::io::Error::other("prefetch thread panicked"));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Remembers `err`, so later reads return it as well
+    fn fail(&mut self, err: std::io::Error) -> std::io::Error {
+        self.failed = Some((err.kind(), err.to_string()));
+        return err;
+    }
+}
+
+impl std::io::Read for Prefetch {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.pos == self.chunk.len() {
+            if let Some((kind, message)) = &self.failed {
+                return Err(std::io::Error::new(*kind, message.clone()));
+            }
+
+            let Some(receiver) = &self.receiver else {
+                return Ok(0);
+            };
+
+            match receiver.recv() {
+                Ok(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                Ok(Err(err)) => return Err(self.fail(err)),
+                Err(_) => {
+                    // The thread has finished
+                    self.receiver = None;
+                    if let Err(err) = self.join() {
+                        return Err(self.fail(err));
+                    }
+
+                    return Ok(0);
+                }
+            }
+        }
+
+        let consumed = buf.len().min(self.chunk.len() - self.pos);
+        buf[..consumed].copy_from_slice(&self.chunk[self.pos..(self.pos + consumed)]);
+        self.pos += consumed;
+        return Ok(consumed);
+    }
+}
+
+impl Drop for Prefetch {
+    /// Stops the background thread and waits for it
+    ///
+    /// Blocks until a read of the inner reader, which is currently in
+    /// progress, returns.
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wakes the thread up if it waits for space in the queue
+        self.receiver = None;
+        self.join().ok();
+    }
+}
+
+impl PeekRead<Prefetch> {
+    /// Creates a [`PeekRead`], which reads `read` on a background thread
+    ///
+    /// See [`Prefetch`] for details.
+    pub fn prefetch<Read: std::io::Read + Send + 'static>(read: Read) -> Self {
+        return Self::new(Prefetch::new(read));
+    }
+}
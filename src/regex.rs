@@ -0,0 +1,112 @@
+use crate::{CheckpointMode, PeekRead};
+use regex_automata::dfa::Automaton;
+
+/// Regex for [`PeekRead::peek_match`]
+///
+/// Compiled into an anchored DFA once, so it can be reused for every match.
+#[derive(Debug, Clone)]
+pub struct Regex {
+    dfa: regex_automata::dfa::dense::DFA<alloc::vec::Vec<u32>>,
+}
+
+impl Regex {
+    pub fn new(
+        pattern: &str,
+    ) -> Result<Self, alloc::boxed::Box<regex_automata::dfa::dense::BuildError>> {
+        let dfa = regex_automata::dfa::dense::Builder::new()
+            .configure(
+                regex_automata::dfa::dense::Config::new()
+                    .start_kind(regex_automata::dfa::StartKind::Anchored),
+            )
+            .build(pattern)
+            .map_err(alloc::boxed::Box::new)?;
+        return Ok(Self { dfa });
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Matches `regex` against the start of the lookahead
+    ///
+    /// Returns the length of the match (like the `regex` crate, the
+    /// leftmost-first one), or `None` if the lookahead doesn't match. Feeds
+    /// the DFA byte by byte and reads the inner reader only as far as the DFA
+    /// needs it. Doesn't consume anything.
+    ///
+    /// `max_scan` limits the length of the match: fails with `InvalidData` if
+    /// the regex may match more than `max_scan` bytes. The DFA reports a match
+    /// one byte late and needs one more byte to rule out a longer match, so
+    /// up to `max_scan + 2` bytes are buffered.
+    pub fn peek_match(
+        &mut self,
+        regex: &Regex,
+        max_scan: usize,
+    ) -> crate::io::Result<Option<usize>> {
+        let dfa = &regex.dfa;
+        let config =
+            regex_automata::util::start::Config::new().anchored(regex_automata::Anchored::Yes);
+        let mut sid = dfa
+            .start_state(&config)
+            .expect("anchored searches are supported");
+        let exceeded = || {
+            crate::io::Error::new(
+                crate::io::ErrorKind::InvalidData,
+                "regex match exceeds the scan limit",
+            )
+        };
+
+        let mut matched = None;
+        let mut scanned = 0;
+        loop {
+            let pos = self.pos.unwrap_or(0);
+            if scanned < self.data().len() - pos {
+                sid = dfa.next_state(sid, self.data()[pos + scanned]);
+            } else if self.scan_more(scanned, max_scan.saturating_add(2))? {
+                continue;
+            } else {
+                sid = dfa.next_eoi_state(sid);
+            }
+
+            // Matches are reported one byte late
+            if dfa.is_match_state(sid) {
+                if scanned > max_scan {
+                    return Err(exceeded());
+                }
+
+                matched = Some(scanned);
+            } else if dfa.is_dead_state(sid) {
+                return Ok(matched);
+            } else if dfa.is_quit_state(sid) {
+                return Err(crate::io::Error::new(
+                    crate::io::ErrorKind::InvalidInput,
+                    "regex can't be matched by a DFA",
+                ));
+            }
+
+            if scanned == self.data().len() - pos {
+                // End of the stream
+                return Ok(matched);
+            }
+
+            if scanned > max_scan {
+                // A longer match is still possible
+                return Err(exceeded());
+            }
+
+            scanned += 1;
+        }
+    }
+
+    /// Like [`PeekRead::peek_match`], but consumes the match
+    pub fn consume_match(
+        &mut self,
+        regex: &Regex,
+        max_scan: usize,
+    ) -> crate::io::Result<Option<usize>> {
+        let matched = self.peek_match(regex, max_scan)?;
+        if let Some(len) = matched {
+            self.consume_buffer_bytes(len);
+        }
+
+        return Ok(matched);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{CheckpointMode, PeekRead};
+
+/// Bytes read from the inner reader at once, while searching
+const SCAN_SIZE: usize = 8 * 1024;
+
+/// Result of [`PeekRead::scan_for`]
+pub(crate) enum Scan {
+    /// Offset of the delimiter
+    Found(usize),
+    /// The stream ended before the delimiter
+    Eof,
+    /// `max` bytes were scanned, the stream may continue
+    Limit,
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Buffers more bytes for a search, which already scanned `scanned` bytes
+    ///
+    /// Returns false at the end of the stream.
+    pub(crate) fn scan_more(&mut self, scanned: usize, max: usize) -> crate::io::Result<bool> {
+        let pos = self.pos.unwrap_or(0);
+        if self.read_chunk((max - scanned).min(SCAN_SIZE))? == 0 {
+            return Ok(false);
+        }
+
+        self.pos = Some(pos);
+        return Ok(true);
+    }
+
+    /// Returns the offset of `delim` in the lookahead
+    ///
+    /// Scans at most `max` bytes, reading the inner reader only as far as
+    /// required. Returns `None` if the stream ended or the limit was reached
+    /// before `delim` was found. Doesn't consume anything.
+    pub fn position_of(&mut self, delim: u8, max: usize) -> crate::io::Result<Option<usize>> {
+        return match self.scan_for(delim, max)? {
+            Scan::Found(offset) => Ok(Some(offset)),
+            Scan::Eof | Scan::Limit => Ok(None),
+        };
+    }
+
+    /// Searches `delim` like [`PeekRead::position_of`], but tells why it
+    /// stopped
+    pub(crate) fn scan_for(&mut self, delim: u8, max: usize) -> crate::io::Result<Scan> {
+        // Already scanned bytes aren't scanned again after a refill
+        let mut scanned = 0;
+        loop {
+            let pos = self.pos.unwrap_or(0);
+            let end = self.data().len().min(pos.saturating_add(max));
+            if let Some(offset) = memchr::memchr(delim, &self.data()[pos + scanned..end]) {
+                return Ok(Scan::Found(scanned + offset));
+            }
+
+            scanned = end - pos;
+            if scanned >= max {
+                return Ok(Scan::Limit);
+            }
+
+            if !self.scan_more(scanned, max)? {
+                return Ok(Scan::Eof);
+            }
+        }
+    }
+
+    /// Peeks the stream up to and including `delim`
+    ///
+    /// Returns at most `max` bytes: the result ends with `delim`, unless the
+    /// stream ended or the limit was reached before. Doesn't consume anything.
+    pub fn peek_until(&mut self, delim: u8, max: usize) -> crate::io::Result<&[u8]> {
+        let len = match self.position_of(delim, max)? {
+            Some(offset) => offset + 1,
+            None => (self.data().len() - self.pos.unwrap_or(0)).min(max),
+        };
+
+        let pos = self.pos.unwrap_or(0);
+        return Ok(&self.data()[pos..(pos + len)]);
+    }
+
+    /// Consumes the stream up to and including `delim`
+    ///
+    /// Discards the bytes without copying them out and returns how many were
+    /// consumed. Stops at the end of the stream. Fails with `InvalidData` if
+    /// `delim` isn't within the next `max_scan` bytes. On errors (also of the
+    /// inner reader), the bytes skipped so far stay consumed.
+    pub fn skip_until(&mut self, delim: u8, max_scan: usize) -> crate::io::Result<usize> {
+        let mut consumed = 0;
+        loop {
+            let pos = self.pos.unwrap_or(0);
+            let buffered = &self.data()[pos..];
+            let len = buffered.len().min(max_scan - consumed);
+            if let Some(offset) = memchr::memchr(delim, &buffered[..len]) {
+                self.consume_buffer_bytes(offset + 1);
+                return Ok(consumed + offset + 1);
+            }
+
+            self.consume_buffer_bytes(len);
+            consumed += len;
+            if consumed >= max_scan {
+                return Err(crate::io::Error::new(
+                    crate::io::ErrorKind::InvalidData,
+                    "delimiter exceeds the scan limit",
+                ));
+            }
+
+            if !self.scan_more(consumed, max_scan)? {
+                return Ok(consumed);
+            }
+        }
+    }
+}
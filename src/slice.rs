@@ -0,0 +1,186 @@
+/// Peekable for in-memory data
+///
+/// Offers the same operations as [`crate::PeekRead`], but never copies into
+/// an internal buffer: peeks borrow from the original slice and checkpoints
+/// only store offsets. Unlike [`crate::PeekRead::from_slice`], peeked slices
+/// borrow from the data instead of the reader.
+#[derive(Debug, Clone, Copy)]
+pub struct SlicePeekRead<'a> {
+    data: &'a [u8],
+    pub(crate) pos: usize,
+}
+
+impl<'a> SlicePeekRead<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Starts at the current position of `cursor`
+    #[cfg(feature = "std")]
+    pub fn from_cursor(cursor: std::io::Cursor<&'a [u8]>) -> Self {
+        let pos = (cursor.position() as usize).min(cursor.get_ref().len());
+        Self {
+            data: cursor.into_inner(),
+            pos,
+        }
+    }
+
+    /// Returns the data which wasn't consumed yet
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        return &self.data[self.pos..];
+    }
+
+    /// Returns the number of consumed bytes
+    #[inline]
+    pub fn position(&self) -> usize {
+        return self.pos;
+    }
+
+    /// Peeks the stream
+    ///
+    /// Returns the top of the stream without consuming its contents
+    #[inline]
+    pub fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let consumed = buf.len().min(self.data.len() - self.pos);
+        buf[..consumed].copy_from_slice(&self.data[self.pos..(self.pos + consumed)]);
+        return Ok(consumed);
+    }
+
+    /// Peeks the stream
+    ///
+    /// Returns the top of the stream without consuming its contents
+    #[inline]
+    pub fn peek_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        buf.copy_from_slice(self.peek_slice(buf.len())?);
+        return Ok(());
+    }
+
+    /// Peeks exactly `len` bytes of the stream (borrowed from the original data)
+    #[inline]
+    pub fn peek_slice(&self, len: usize) -> crate::io::Result<&'a [u8]> {
+        if self.data.len() - self.pos < len {
+            return Err(crate::io::Error::new(
+                crate::io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            ));
+        }
+
+        return Ok(&self.data[self.pos..(self.pos + len)]);
+    }
+
+    /// Peeks exactly `len` bytes of the stream
+    ///
+    /// In-memory data never blocks, so this never returns `None`.
+    #[inline]
+    pub fn try_peek_exact(&mut self, len: usize) -> crate::io::Result<Option<&'a [u8]>> {
+        return self.peek_slice(len).map(Some);
+    }
+
+    /// Peeks the stream into multiple buffers
+    #[cfg(feature = "std")]
+    pub fn peek_vectored(
+        &mut self,
+        bufs: &mut [std::io::IoSliceMut<'_>],
+    ) -> crate::io::Result<usize> {
+        return Ok(crate::scatter(self.remaining(), bufs));
+    }
+
+    /// Writes the rest of the stream to `writer`
+    #[cfg(feature = "std")]
+    pub fn copy_to<W: std::io::Write + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> crate::io::Result<u64> {
+        let remaining = self.remaining();
+        writer.write_all(remaining)?;
+        self.pos = self.data.len();
+        return Ok(remaining.len() as u64);
+    }
+
+    /// Creates a checkpoint and calls fn_checkpoint afterwards
+    ///
+    /// Resets the reader to the current state if an Error is returned. Can be stacked.
+    pub fn checkpoint<T, E, F: FnOnce(&mut Self) -> Result<T, E>>(
+        &mut self,
+        fn_checkpoint: F,
+    ) -> Result<T, E> {
+        let pos = self.pos;
+        let result = fn_checkpoint(self);
+        if result.is_err() {
+            self.pos = pos;
+        }
+
+        return result;
+    }
+}
+
+impl<'a> From<&'a [u8]> for SlicePeekRead<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        return Self::new(data);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> From<std::io::Cursor<&'a [u8]>> for SlicePeekRead<'a> {
+    fn from(cursor: std::io::Cursor<&'a [u8]>) -> Self {
+        return Self::from_cursor(cursor);
+    }
+}
+
+impl crate::io::Read for SlicePeekRead<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let consumed = self.peek(buf)?;
+        self.pos += consumed;
+        return Ok(consumed);
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        self.peek_exact(buf)?;
+        self.pos += buf.len();
+        return Ok(());
+    }
+
+    #[cfg(feature = "std")]
+    fn read_vectored(&mut self, bufs: &mut [std::io::IoSliceMut<'_>]) -> crate::io::Result<usize> {
+        let consumed = self.peek_vectored(bufs)?;
+        self.pos += consumed;
+        return Ok(consumed);
+    }
+
+    #[cfg(feature = "std")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> crate::io::Result<usize> {
+        let remaining = self.remaining();
+        buf.extend_from_slice(remaining);
+        self.pos = self.data.len();
+        return Ok(remaining.len());
+    }
+
+    #[cfg(feature = "std")]
+    fn read_to_string(&mut self, buf: &mut String) -> crate::io::Result<usize> {
+        let remaining = core::str::from_utf8(self.remaining()).map_err(|_| {
+            crate::io::Error::new(
+                crate::io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )
+        })?;
+        buf.push_str(remaining);
+        self.pos = self.data.len();
+        return Ok(remaining.len());
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::BufRead for SlicePeekRead<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> crate::io::Result<&[u8]> {
+        return Ok(self.remaining());
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.data.len());
+    }
+}
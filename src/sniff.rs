@@ -0,0 +1,282 @@
+use crate::{CheckpointMode, PeekRead};
+use alloc::vec::Vec;
+
+/// File format detected by [`PeekRead::sniff`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    Gzip,
+    Zstd,
+    Xz,
+    Bzip2,
+    Zip,
+    /// POSIX tar (`ustar` at offset 257)
+    Tar,
+    Png,
+    Jpeg,
+    Gif,
+    Pdf,
+    Elf,
+    /// WebAssembly binary
+    Wasm,
+    /// Text with an UTF-8 byte order mark
+    Utf8,
+    /// Text with an UTF-16 little-endian byte order mark
+    Utf16Le,
+    /// Text with an UTF-16 big-endian byte order mark
+    Utf16Be,
+    /// Text starting with `{` followed by a key (`"...":`) or `}`, or `[`
+    /// followed by a JSON value or `]` (after whitespace)
+    Json,
+    /// Text starting with `<?xml` (after whitespace)
+    Xml,
+    /// Signature registered with [`Sniffer::with_signature`]
+    Custom(&'static str),
+}
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Magic numbers of binary formats (format, offset, magic)
+const SIGNATURES: &[(Format, usize, &[u8])] = &[
+    (Format::Gzip, 0, b"\x1f\x8b"),
+    (Format::Zstd, 0, b"\x28\xb5\x2f\xfd"),
+    (Format::Xz, 0, b"\xfd7zXZ\x00"),
+    (Format::Zip, 0, b"PK\x03\x04"),
+    // Empty archive
+    (Format::Zip, 0, b"PK\x05\x06"),
+    (Format::Png, 0, b"\x89PNG\r\n\x1a\n"),
+    (Format::Jpeg, 0, b"\xff\xd8\xff"),
+    (Format::Gif, 0, b"GIF87a"),
+    (Format::Gif, 0, b"GIF89a"),
+    (Format::Pdf, 0, b"%PDF-"),
+    (Format::Elf, 0, b"\x7fELF"),
+    (Format::Wasm, 0, b"\x00asm"),
+];
+
+/// Checked after all other formats, it would delay their detection
+const TAR: (Format, usize, &[u8]) = (Format::Tar, 257, b"ustar");
+
+/// Result of checking a format against the first bytes of a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    Match(Format),
+    Mismatch,
+    /// The bytes end before the check could decide
+    Pending,
+}
+
+/// Checks whether `bytes` contains `magic` at `offset`
+fn check_magic(bytes: &[u8], format: Format, offset: usize, magic: &[u8]) -> Check {
+    let bytes = bytes.get(offset..).unwrap_or_default();
+    if bytes.len() < magic.len() && magic.starts_with(bytes) {
+        return Check::Pending;
+    }
+
+    if bytes.starts_with(magic) {
+        return Check::Match(format);
+    }
+
+    return Check::Mismatch;
+}
+
+/// Checks whether `bytes` starts with `BZh` and the block size (`1` to `9`)
+fn check_bzip2(bytes: &[u8]) -> Check {
+    return match (check_magic(bytes, Format::Bzip2, 0, b"BZh"), bytes.get(3)) {
+        (Check::Match(_), None) => Check::Pending,
+        (Check::Match(format), Some(b'1'..=b'9')) => Check::Match(format),
+        (Check::Match(_), Some(_)) => Check::Mismatch,
+        (check, _) => check,
+    };
+}
+
+/// Returns `bytes` without leading whitespace, `None` if nothing is left
+fn trim_start(bytes: &[u8]) -> Option<&[u8]> {
+    let start = bytes.iter().position(|byte| !byte.is_ascii_whitespace())?;
+    return Some(&bytes[start..]);
+}
+
+/// Checks whether `text` starts like a JSON document
+///
+/// `{` also starts RTF (`{\rtf1`) and `[` INI sections, so they need a key or
+/// a value after them.
+fn check_json(text: &[u8]) -> Check {
+    let Some((first, rest)) = text.split_first() else {
+        return Check::Pending;
+    };
+    let Some(value) = trim_start(rest) else {
+        return Check::Pending;
+    };
+
+    return match (first, value[0]) {
+        (b'{', b'}') | (b'[', b'{' | b'[' | b'"' | b'-' | b'0'..=b'9' | b']') => {
+            Check::Match(Format::Json)
+        }
+        (b'{', b'"') => check_json_key(&value[1..]),
+        (b'[', _) => [b"true".as_ref(), b"false", b"null"]
+            .iter()
+            .map(|literal| check_magic(value, Format::Json, 0, literal))
+            .find(|check| *check != Check::Mismatch)
+            .unwrap_or(Check::Mismatch),
+        _ => Check::Mismatch,
+    };
+}
+
+/// Checks whether `text` continues a key (after `"`) with `":`
+fn check_json_key(text: &[u8]) -> Check {
+    let mut bytes = text.iter();
+    loop {
+        match bytes.next() {
+            None => return Check::Pending,
+            Some(b'"') => break,
+            Some(b'\\') => {
+                if bytes.next().is_none() {
+                    return Check::Pending;
+                }
+            }
+            // Control characters must be escaped
+            Some(0..=0x1f) => return Check::Mismatch,
+            Some(_) => {}
+        }
+    }
+
+    return match trim_start(bytes.as_slice()) {
+        None => Check::Pending,
+        Some([b':', ..]) => Check::Match(Format::Json),
+        Some(_) => Check::Mismatch,
+    };
+}
+
+/// Checks whether `bytes` starts with XML or JSON text (after whitespace and
+/// an UTF-8 byte order mark)
+fn check_text(bytes: &[u8]) -> Check {
+    if bytes.len() < UTF8_BOM.len() && UTF8_BOM.starts_with(bytes) {
+        return Check::Pending;
+    }
+
+    let text = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    return match trim_start(text) {
+        None => Check::Pending,
+        Some(text @ [b'<', ..]) => check_magic(text, Format::Xml, 0, b"<?xml"),
+        Some(text) => check_json(text),
+    };
+}
+
+/// Byte order marks, checked after JSON and XML
+const BOMS: &[(Format, &[u8])] = &[
+    (Format::Utf8, UTF8_BOM),
+    (Format::Utf16Le, b"\xff\xfe"),
+    (Format::Utf16Be, b"\xfe\xff"),
+];
+
+/// Bytes peeked for the built-in signatures (up to `ustar` of tar)
+const SNIFF_LEN: usize = 262;
+
+/// Detects file formats, extendable with custom signatures
+///
+/// Custom signatures are checked before the built-in ones, in the order they
+/// were added.
+#[derive(Debug, Clone, Default)]
+pub struct Sniffer {
+    signatures: Vec<(Format, usize, Vec<u8>)>,
+}
+
+impl Sniffer {
+    /// Creates a Sniffer, which only knows the built-in formats
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Detects `magic` at `offset` as `Format::Custom(name)`
+    pub fn with_signature(
+        mut self,
+        name: &'static str,
+        offset: usize,
+        magic: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.signatures
+            .push((Format::Custom(name), offset, magic.into()));
+        return self;
+    }
+
+    /// Returns the number of bytes required to check all signatures
+    fn len(&self) -> usize {
+        return self
+            .signatures
+            .iter()
+            .map(|(_, offset, magic)| offset + magic.len())
+            .fold(SNIFF_LEN, usize::max);
+    }
+
+    /// Detects the format of `bytes`, the start of a stream
+    pub fn sniff(&self, bytes: &[u8]) -> Option<Format> {
+        return self.detect(bytes, true).flatten();
+    }
+
+    /// Detects the format of `bytes`, returns `None` if more bytes are
+    /// required
+    ///
+    /// The formats are checked by priority, the first one which matches is
+    /// returned. A check, which can't decide yet, only counts as a mismatch if
+    /// the stream is `complete`.
+    fn detect(&self, bytes: &[u8], complete: bool) -> Option<Option<Format>> {
+        let custom = self
+            .signatures
+            .iter()
+            .map(|(format, offset, magic)| (*format, *offset, magic.as_slice()));
+        let signatures = custom
+            .chain(SIGNATURES.iter().copied())
+            .map(|(format, offset, magic)| check_magic(bytes, format, offset, magic));
+        let boms = BOMS
+            .iter()
+            .map(|(format, bom)| check_magic(bytes, *format, 0, bom));
+        let (format, offset, magic) = TAR;
+        let checks = signatures
+            .chain([check_bzip2(bytes), check_text(bytes)])
+            .chain(boms)
+            .chain([check_magic(bytes, format, offset, magic)]);
+
+        for check in checks {
+            match check {
+                Check::Match(format) => return Some(Some(format)),
+                Check::Pending if !complete => return None,
+                Check::Mismatch | Check::Pending => {}
+            }
+        }
+
+        return Some(None);
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Detects the format of the stream by its magic numbers
+    ///
+    /// Peeks up to 262 bytes, nothing is consumed. Returns `None` if the
+    /// format is unknown. The inner reader is only read while the buffered
+    /// bytes don't decide the format yet, e.g. `{"a":1}` is detected as JSON
+    /// without waiting for more bytes.
+    pub fn sniff(&mut self) -> crate::io::Result<Option<Format>> {
+        return self.sniff_with(&Sniffer::new());
+    }
+
+    /// Detects the format of the stream, including custom signatures
+    ///
+    /// Peeks as far as the signatures reach, nothing is consumed. Custom
+    /// signatures are checked first, so the buffered bytes only decide the
+    /// format once they reach past the custom signatures (or mismatch them).
+    pub fn sniff_with(&mut self, sniffer: &Sniffer) -> crate::io::Result<Option<Format>> {
+        let len = sniffer.len();
+        let pos = self.pos.unwrap_or(0);
+        loop {
+            let available = (self.data().len() - pos).min(len);
+            let bytes = &self.data()[pos..(pos + available)];
+            if let Some(format) = sniffer.detect(bytes, available == len) {
+                return Ok(format);
+            }
+
+            if !self.scan_more(available, len)? {
+                let bytes = &self.data()[pos..(pos + available)];
+                return Ok(sniffer.sniff(bytes));
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{CheckpointMode, Checkpoints, PeekRead, SlicePeekRead};
+
+/// Sources which can be peeked without consuming their contents
+///
+/// Allows parsers to be generic over [`PeekRead`], [`SlicePeekRead`],
+/// `&[u8]`, `Cursor` and `BufReader`. The trait is object-safe.
+pub trait Peek {
+    /// Peeks the stream
+    ///
+    /// Returns the top of the stream without consuming its contents
+    fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize>;
+
+    /// Peeks exactly `len` bytes of the stream
+    fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]>;
+
+    /// Peeks the stream
+    ///
+    /// Returns the top of the stream without consuming its contents
+    fn peek_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        buf.copy_from_slice(self.peek_slice(buf.len())?);
+        return Ok(());
+    }
+}
+
+/// Position of a source, see [`Checkpoint::mark`]
+#[derive(Debug)]
+pub struct Mark(u64);
+
+/// Sources which can be reset to an earlier position
+///
+/// Marks have to be reset or released in reverse order of their creation.
+/// `&[u8]` can't implement this trait, because consuming it forgets the
+/// consumed bytes; use [`SlicePeekRead`] instead. The trait is object-safe,
+/// except for [`Checkpoint::checkpoint`].
+pub trait Checkpoint {
+    /// Remembers the current position
+    fn mark(&mut self) -> crate::io::Result<Mark>;
+
+    /// Returns to the position of `mark` and releases it
+    fn reset(&mut self, mark: Mark) -> crate::io::Result<()>;
+
+    /// Releases `mark` without changing the position
+    fn release(&mut self, mark: Mark);
+
+    /// Creates a checkpoint and calls fn_checkpoint afterwards
+    ///
+    /// Resets the reader to the current state if an Error is returned. Can be
+    /// stacked. Returns the error of the reset instead, if that fails.
+    fn checkpoint<T, E: From<crate::io::Error>, F: FnOnce(&mut Self) -> Result<T, E>>(
+        &mut self,
+        fn_checkpoint: F,
+    ) -> Result<T, E>
+    where
+        Self: Sized,
+    {
+        let mark = self.mark()?;
+        let result = fn_checkpoint(self);
+        return match result {
+            Ok(result) => {
+                self.release(mark);
+                Ok(result)
+            }
+            Err(err) => {
+                self.reset(mark)?;
+                Err(err)
+            }
+        };
+    }
+}
+
+fn invalid_mark() -> crate::io::Error {
+    return crate::io::Error::new(
+        crate::io::ErrorKind::InvalidInput,
+        "invalid checkpoint mark",
+    );
+}
+
+fn unexpected_eof() -> crate::io::Error {
+    return crate::io::Error::new(
+        crate::io::ErrorKind::UnexpectedEof,
+        "failed to fill whole buffer",
+    );
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> Peek for PeekRead<Read, Mode> {
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        return PeekRead::peek(self, buf);
+    }
+
+    #[inline]
+    fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]> {
+        return PeekRead::peek_slice(self, len);
+    }
+
+    #[inline]
+    fn peek_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        return PeekRead::peek_exact(self, buf);
+    }
+}
+
+impl<Read: crate::io::Read + ?Sized> Checkpoint for PeekRead<Read, Checkpoints> {
+    #[inline]
+    fn mark(&mut self) -> crate::io::Result<Mark> {
+        self.checkpoints.push(self.pos.unwrap_or(0));
+        return Ok(Mark(self.checkpoints.len() as u64 - 1));
+    }
+
+    fn reset(&mut self, mark: Mark) -> crate::io::Result<()> {
+        let depth = mark.0 as usize;
+        if depth >= self.checkpoints.len() {
+            return Err(invalid_mark());
+        }
+
+        self.pos = Some(self.checkpoints[depth]);
+        self.checkpoints.truncate(depth);
+        return Ok(());
+    }
+
+    #[inline]
+    fn release(&mut self, mark: Mark) {
+        self.checkpoints.truncate(mark.0 as usize);
+    }
+}
+
+impl Peek for SlicePeekRead<'_> {
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        return SlicePeekRead::peek(self, buf);
+    }
+
+    #[inline]
+    fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]> {
+        return SlicePeekRead::peek_slice(self, len);
+    }
+}
+
+impl Checkpoint for SlicePeekRead<'_> {
+    #[inline]
+    fn mark(&mut self) -> crate::io::Result<Mark> {
+        return Ok(Mark(self.position() as u64));
+    }
+
+    fn reset(&mut self, mark: Mark) -> crate::io::Result<()> {
+        if mark.0 > self.position() as u64 {
+            return Err(invalid_mark());
+        }
+
+        self.pos = mark.0 as usize;
+        return Ok(());
+    }
+
+    #[inline]
+    fn release(&mut self, _mark: Mark) {}
+}
+
+impl Peek for &[u8] {
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let consumed = buf.len().min(self.len());
+        buf[..consumed].copy_from_slice(&self[..consumed]);
+        return Ok(consumed);
+    }
+
+    #[inline]
+    fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]> {
+        if self.len() < len {
+            return Err(unexpected_eof());
+        }
+
+        return Ok(&self[..len]);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: AsRef<[u8]>> Peek for std::io::Cursor<T> {
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let mut remaining = cursor_remaining(self);
+        return Peek::peek(&mut remaining, buf);
+    }
+
+    #[inline]
+    fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]> {
+        let remaining = cursor_remaining(self);
+        if remaining.len() < len {
+            return Err(unexpected_eof());
+        }
+
+        return Ok(&remaining[..len]);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: AsRef<[u8]>> Checkpoint for std::io::Cursor<T> {
+    #[inline]
+    fn mark(&mut self) -> crate::io::Result<Mark> {
+        return Ok(Mark(self.position()));
+    }
+
+    #[inline]
+    fn reset(&mut self, mark: Mark) -> crate::io::Result<()> {
+        self.set_position(mark.0);
+        return Ok(());
+    }
+
+    #[inline]
+    fn release(&mut self, _mark: Mark) {}
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn cursor_remaining<T: AsRef<[u8]>>(cursor: &std::io::Cursor<T>) -> &[u8] {
+    let data = cursor.get_ref().as_ref();
+    let pos = (cursor.position() as usize).min(data.len());
+    return &data[pos..];
+}
+
+#[cfg(feature = "std")]
+impl<Read: crate::io::Read + std::io::Seek> Peek for std::io::BufReader<Read> {
+    /// Peeks the stream
+    ///
+    /// Returns at most the bytes the `BufReader` has buffered (after filling
+    /// its buffer, if it was empty).
+    fn peek(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let mut buffer = std::io::BufRead::fill_buf(self)?;
+        return Peek::peek(&mut buffer, buf);
+    }
+
+    /// Peeks exactly `len` bytes of the stream
+    ///
+    /// Refills the buffer of the `BufReader` (seeking back the inner reader)
+    /// if less than `len` bytes are buffered. Fails with `InvalidInput` if
+    /// `len` exceeds the capacity of the `BufReader`, and with
+    /// `UnexpectedEof` if the refilled buffer is still too short.
+    fn peek_slice(&mut self, len: usize) -> crate::io::Result<&[u8]> {
+        if self.buffer().len() < len {
+            if self.capacity() < len {
+                return Err(crate::io::Error::new(
+                    crate::io::ErrorKind::InvalidInput,
+                    "peek exceeds the capacity of the BufReader",
+                ));
+            }
+
+            // Discards the buffer, but keeps the position
+            std::io::Seek::seek(self, std::io::SeekFrom::Current(0))?;
+        }
+
+        let buffer = std::io::BufRead::fill_buf(self)?;
+        if buffer.len() < len {
+            return Err(unexpected_eof());
+        }
+
+        return Ok(&buffer[..len]);
+    }
+
+    /// Peeks the stream
+    ///
+    /// Reads the bytes which aren't buffered and seeks back afterwards.
+    fn peek_exact(&mut self, buf: &mut [u8]) -> crate::io::Result<()> {
+        if self.buffer().len() >= buf.len() {
+            buf.copy_from_slice(&self.buffer()[..buf.len()]);
+            return Ok(());
+        }
+
+        let start = std::io::Seek::stream_position(self)?;
+        match crate::io::Read::read_exact(self, buf) {
+            Ok(()) => return self.seek_relative(-(buf.len() as i64)),
+            Err(err) => {
+                std::io::Seek::seek(self, std::io::SeekFrom::Start(start))?;
+                return Err(err);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Read: crate::io::Read + std::io::Seek> Checkpoint for std::io::BufReader<Read> {
+    #[inline]
+    fn mark(&mut self) -> crate::io::Result<Mark> {
+        return Ok(Mark(std::io::Seek::stream_position(self)?));
+    }
+
+    /// Returns to the position of `mark`
+    ///
+    /// Keeps the buffer of the `BufReader` if the position is still buffered.
+    fn reset(&mut self, mark: Mark) -> crate::io::Result<()> {
+        let pos = std::io::Seek::stream_position(self)?;
+        return self.seek_relative(mark.0 as i64 - pos as i64);
+    }
+
+    #[inline]
+    fn release(&mut self, _mark: Mark) {}
+}
@@ -0,0 +1,70 @@
+use crate::{CheckpointMode, PeekRead};
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Peeks the next `N` bytes of the stream
+    ///
+    /// Like [`PeekRead::peek_exact`], but returns an array.
+    #[inline]
+    pub fn peek_array<const N: usize>(&mut self) -> crate::io::Result<[u8; N]> {
+        self.fill_buffer(N)?;
+
+        let pos = self.pos.unwrap_or(0);
+        let mut result = [0; N];
+        result.copy_from_slice(&self.data()[pos..(pos + N)]);
+        return Ok(result);
+    }
+
+    /// Reads the next `N` bytes of the stream
+    ///
+    /// Nothing is consumed if the stream ended before.
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> crate::io::Result<[u8; N]> {
+        let result = self.peek_array()?;
+        self.consume_buffer_bytes(N);
+        return Ok(result);
+    }
+}
+
+/// Generates `peek_*` and `read_*` for numbers
+macro_rules! numbers {
+    ($($ty:ident: $peek:ident, $read:ident, $from_bytes:ident, $order:literal;)*) => {
+        impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+            $(
+                #[doc = concat!("Peeks ", $order, " `", stringify!($ty), "`")]
+                #[inline]
+                pub fn $peek(&mut self) -> crate::io::Result<$ty> {
+                    return Ok($ty::$from_bytes(self.peek_array()?));
+                }
+
+                #[doc = concat!("Reads ", $order, " `", stringify!($ty), "`")]
+                ///
+                /// Nothing is consumed if the stream ended before.
+                #[inline]
+                pub fn $read(&mut self) -> crate::io::Result<$ty> {
+                    return Ok($ty::$from_bytes(self.read_array()?));
+                }
+            )*
+        }
+    };
+}
+
+numbers! {
+    u8: peek_u8, read_u8, from_le_bytes, "an";
+    i8: peek_i8, read_i8, from_le_bytes, "an";
+    u16: peek_u16_le, read_u16_le, from_le_bytes, "a little-endian";
+    u16: peek_u16_be, read_u16_be, from_be_bytes, "a big-endian";
+    i16: peek_i16_le, read_i16_le, from_le_bytes, "a little-endian";
+    i16: peek_i16_be, read_i16_be, from_be_bytes, "a big-endian";
+    u32: peek_u32_le, read_u32_le, from_le_bytes, "a little-endian";
+    u32: peek_u32_be, read_u32_be, from_be_bytes, "a big-endian";
+    i32: peek_i32_le, read_i32_le, from_le_bytes, "a little-endian";
+    i32: peek_i32_be, read_i32_be, from_be_bytes, "a big-endian";
+    u64: peek_u64_le, read_u64_le, from_le_bytes, "a little-endian";
+    u64: peek_u64_be, read_u64_be, from_be_bytes, "a big-endian";
+    i64: peek_i64_le, read_i64_le, from_le_bytes, "a little-endian";
+    i64: peek_i64_be, read_i64_be, from_be_bytes, "a big-endian";
+    f32: peek_f32_le, read_f32_le, from_le_bytes, "a little-endian";
+    f32: peek_f32_be, read_f32_be, from_be_bytes, "a big-endian";
+    f64: peek_f64_le, read_f64_le, from_le_bytes, "a little-endian";
+    f64: peek_f64_be, read_f64_be, from_be_bytes, "a big-endian";
+}
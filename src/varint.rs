@@ -0,0 +1,96 @@
+use crate::{CheckpointMode, PeekRead};
+use alloc::vec::Vec;
+
+/// Maximum length of a varint encoding a `u64`
+const MAX_VARINT_LEN: usize = 10;
+
+#[inline]
+fn invalid_data(message: &'static str) -> crate::io::Error {
+    return crate::io::Error::new(crate::io::ErrorKind::InvalidData, message);
+}
+
+#[inline]
+fn zigzag(value: u64) -> i64 {
+    return (value >> 1) as i64 ^ -((value & 1) as i64);
+}
+
+impl<Read: crate::io::Read + ?Sized, Mode: CheckpointMode> PeekRead<Read, Mode> {
+    /// Peeks a varint, returns its value and its length in bytes
+    ///
+    /// Reads the inner reader only as far as the varint reaches.
+    fn peek_varint(&mut self) -> crate::io::Result<(u64, usize)> {
+        let mut value = 0u64;
+        for len in 1..=MAX_VARINT_LEN {
+            self.fill_buffer(len)?;
+
+            let byte = self.data()[self.pos.unwrap_or(0) + len - 1];
+            if len == MAX_VARINT_LEN && byte > 1 {
+                return Err(invalid_data("varint overflows u64"));
+            }
+
+            value |= ((byte & 0x7f) as u64) << (7 * (len - 1));
+            if byte & 0x80 == 0 {
+                return Ok((value, len));
+            }
+        }
+
+        return Err(invalid_data("varint overflows u64"));
+    }
+
+    /// Peeks an unsigned varint (LEB128, like in Protobuf)
+    ///
+    /// Padded varints (like `80 80 80 80 00` in WebAssembly) are accepted up
+    /// to 10 bytes. Fails with `InvalidData` on varints, which overflow a
+    /// `u64` or are longer, and with `UnexpectedEof` on truncated ones.
+    pub fn peek_varint_u64(&mut self) -> crate::io::Result<u64> {
+        return Ok(self.peek_varint()?.0);
+    }
+
+    /// Reads an unsigned varint, see [`PeekRead::peek_varint_u64`]
+    ///
+    /// Nothing is consumed if the varint is malformed or truncated.
+    pub fn read_varint_u64(&mut self) -> crate::io::Result<u64> {
+        let (value, len) = self.peek_varint()?;
+        self.consume_buffer_bytes(len);
+        return Ok(value);
+    }
+
+    /// Peeks a zigzag-encoded signed varint (like Protobuf's `sint64`)
+    pub fn peek_varint_i64(&mut self) -> crate::io::Result<i64> {
+        return Ok(zigzag(self.peek_varint()?.0));
+    }
+
+    /// Reads a zigzag-encoded signed varint
+    ///
+    /// Nothing is consumed if the varint is malformed or truncated.
+    pub fn read_varint_i64(&mut self) -> crate::io::Result<i64> {
+        return Ok(zigzag(self.read_varint_u64()?));
+    }
+
+    /// Reads a message, which is prefixed by its length as varint
+    ///
+    /// Peeks the length first, so nothing is consumed if the message is
+    /// truncated. Fails with `InvalidData` if the length exceeds `max_len`.
+    /// The whole message is buffered (the buffer grows to the length of the
+    /// prefix up front), so keep `max_len` as small as the protocol allows.
+    pub fn read_varint_delimited(&mut self, max_len: usize) -> crate::io::Result<Vec<u8>> {
+        let (len, prefix) = self.peek_varint()?;
+        let total = usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= max_len)
+            .and_then(|len| len.checked_add(prefix))
+            .filter(|total| self.pos.unwrap_or(0).checked_add(*total).is_some());
+        let Some(total) = total else {
+            return Err(invalid_data(
+                "varint-delimited message exceeds the maximum length",
+            ));
+        };
+
+        self.fill_buffer(total)?;
+
+        let pos = self.pos.unwrap_or(0);
+        let message = self.data()[(pos + prefix)..(pos + total)].to_vec();
+        self.consume_buffer_bytes(total);
+        return Ok(message);
+    }
+}
@@ -0,0 +1,168 @@
+#![cfg(feature = "futures-io")]
+
+use futures::executor::block_on;
+use futures::io::{AsyncRead, AsyncReadExt, Cursor};
+use futures::FutureExt;
+use peekbufread::{AsyncPeekRead, FuturesPeekRead};
+#[cfg(feature = "async-closures")]
+use std::io::ErrorKind;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Returns its data, afterwards it is pending forever
+struct Stall {
+    data: &'static [u8],
+}
+
+impl AsyncRead for Stall {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.data.is_empty() {
+            return Poll::Pending;
+        }
+
+        let len = buf.len().min(self.data.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Poll::Ready(Ok(len))
+    }
+}
+
+#[test]
+#[cfg(feature = "async-closures")]
+fn checkpoint_async_rollback() {
+    block_on(async {
+        let mut read = AsyncPeekRead::from_futures_io(Cursor::new(b"hello, world"));
+
+        let result = read
+            .checkpoint_async(async |read| -> std::io::Result<()> {
+                let mut buf: [u8; 7] = [0; 7];
+                read.read_exact(&mut buf).await?;
+                assert_eq!(b"hello, ", &buf);
+                Err(ErrorKind::InvalidData.into())
+            })
+            .await;
+        assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+        let mut buf = Vec::new();
+        let result = read.read_to_end(&mut buf).await;
+        assert!(result.is_ok());
+        assert_eq!(b"hello, world", buf.as_slice());
+    });
+}
+
+#[test]
+#[cfg(feature = "async-closures")]
+fn checkpoint_async_commit() {
+    block_on(async {
+        let mut read = AsyncPeekRead::from_futures_io(Cursor::new(b"hello, world"));
+
+        let result = read
+            .checkpoint_async(async |read| -> std::io::Result<[u8; 7]> {
+                let mut buf: [u8; 7] = [0; 7];
+                read.read_exact(&mut buf).await?;
+
+                // Stacked
+                let result = read
+                    .checkpoint_async(async |read| -> std::io::Result<()> {
+                        let mut buf: [u8; 5] = [0; 5];
+                        read.read_exact(&mut buf).await?;
+                        Err(ErrorKind::InvalidData.into())
+                    })
+                    .await;
+                assert!(result.is_err());
+                Ok(buf)
+            })
+            .await;
+        assert_eq!(b"hello, ", &result.unwrap());
+
+        let mut buf = Vec::new();
+        let result = read.read_to_end(&mut buf).await;
+        assert!(result.is_ok());
+        assert_eq!(b"world", buf.as_slice());
+    });
+}
+
+#[test]
+#[cfg(feature = "async-closures")]
+fn checkpoint_async_dropped() {
+    let mut read = AsyncPeekRead::from_futures_io(Stall {
+        data: b"hello, world",
+    });
+
+    let result = read
+        .checkpoint_async(async |read| -> std::io::Result<()> {
+            let mut buf: [u8; 12] = [0; 12];
+            read.read_exact(&mut buf).await?;
+            // Never completes
+            read.peek_slice(1).await?;
+            Ok(())
+        })
+        .now_or_never();
+    assert!(result.is_none());
+
+    let result = read.peek_slice(12).now_or_never();
+    assert_eq!(b"hello, world", result.unwrap().unwrap());
+}
+
+#[test]
+fn checkpoint_guard() {
+    block_on(async {
+        let mut read = AsyncPeekRead::from_futures_io(Cursor::new(b"hello, world"));
+
+        {
+            let mut guard = read.checkpoint_guard();
+            let mut buf: [u8; 7] = [0; 7];
+            let result = guard.read_exact(&mut buf).await;
+            assert!(result.is_ok());
+        }
+
+        let mut guard = read.checkpoint_guard();
+        let mut buf: [u8; 7] = [0; 7];
+        let result = guard.read_exact(&mut buf).await;
+        assert!(result.is_ok());
+        assert_eq!(b"hello, ", &buf);
+        guard.commit();
+
+        let mut buf = Vec::new();
+        let result = read.read_to_end(&mut buf).await;
+        assert!(result.is_ok());
+        assert_eq!(b"world", buf.as_slice());
+    });
+}
+
+#[test]
+fn futures_peek_read_alias() {
+    block_on(async {
+        let mut read: FuturesPeekRead<_> = AsyncPeekRead::from_futures_io(Cursor::new(b"hello"));
+
+        let result = read.peek_slice(5).await;
+        assert!(result.is_ok());
+        assert_eq!(b"hello", result.unwrap());
+    });
+}
+
+#[test]
+fn checkpoint_guard_dropped() {
+    let mut read = AsyncPeekRead::from_futures_io(Stall {
+        data: b"hello, world",
+    });
+
+    let result = async {
+        let mut guard = read.checkpoint_guard();
+        let mut buf: [u8; 12] = [0; 12];
+        guard.read_exact(&mut buf).await?;
+        // Never completes
+        guard.peek_slice(1).await?;
+        guard.commit();
+        Ok::<(), std::io::Error>(())
+    }
+    .now_or_never();
+    assert!(result.is_none());
+
+    let result = read.peek_slice(12).now_or_never();
+    assert_eq!(b"hello, world", result.unwrap().unwrap());
+}
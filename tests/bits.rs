@@ -0,0 +1,101 @@
+#![cfg(feature = "std")]
+
+use peekbufread::BitPeekRead;
+use std::io::ErrorKind;
+
+#[test]
+fn bits_msb_first() {
+    let test = [0b1010_0110u8, 0b1100_0011];
+    let mut read = BitPeekRead::new(test.as_ref());
+
+    assert_eq!(0b101, read.peek_bits(3).unwrap());
+    assert_eq!(0b101, read.read_bits(3).unwrap());
+    assert_eq!(3, read.bit_position());
+    assert_eq!(0b001_1011, read.read_bits(7).unwrap());
+    assert!(!read.read_bit().unwrap());
+    assert_eq!(11, read.bit_position());
+    assert_eq!(0b0_0011, read.read_bits(5).unwrap());
+
+    let result = read.read_bits(1);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn bits_lsb_first() {
+    let test = [0b1010_0110u8, 0b1100_0011];
+    let mut read = BitPeekRead::lsb_first(test.as_ref());
+
+    assert_eq!(0b110, read.read_bits(3).unwrap());
+    assert_eq!(0b111_0100, read.read_bits(7).unwrap());
+    assert_eq!(0b110000, read.read_bits(6).unwrap());
+}
+
+#[test]
+fn bits_wide() {
+    let test = [0xffu8, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let mut read = BitPeekRead::new(test.as_ref());
+
+    assert_eq!(0xf, read.read_bits(4).unwrap());
+    assert_eq!(0xf0123456789abcde, read.peek_bits(64).unwrap());
+    assert_eq!(0xf0123456789abcde, read.read_bits(64).unwrap());
+    assert_eq!(0xf, read.read_bits(4).unwrap());
+}
+
+#[test]
+fn bits_truncated() {
+    let test = [0xffu8];
+    let mut read = BitPeekRead::new(test.as_ref());
+
+    let result = read.read_bits(9);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    // Nothing consumed
+    assert_eq!(0, read.bit_position());
+    assert_eq!(0xff, read.read_bits(8).unwrap());
+}
+
+#[test]
+fn bits_align() {
+    let test = [0b1000_0000u8, 0x42];
+    let mut read = BitPeekRead::new(test.as_ref());
+
+    assert_eq!(0, read.align_to_byte());
+    assert!(read.read_bit().unwrap());
+    assert_eq!(7, read.align_to_byte());
+    assert_eq!(8, read.bit_position());
+    assert_eq!(0x42, read.read_bits(8).unwrap());
+}
+
+#[test]
+fn bits_checkpoint() {
+    let test = [0b1011_0010u8, 0b0111_1111];
+    let mut read = BitPeekRead::new(test.as_ref());
+    assert_eq!(0b101, read.read_bits(3).unwrap());
+
+    let result = read.checkpoint(|read| -> Result<(), ()> {
+        read.read_bits(6).ok();
+        read.align_to_byte();
+        read.read_bits(4).ok();
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(3, read.bit_position());
+
+    let result = read.checkpoint(|read| read.read_bits(6));
+    assert!(result.is_ok());
+    assert_eq!(0b10_0100, result.unwrap());
+    assert_eq!(9, read.bit_position());
+    assert_eq!(0b111_1111, read.read_bits(7).unwrap());
+}
+
+#[test]
+fn bits_into_inner() {
+    let test = [0x12u8, 0x34];
+    let mut read = BitPeekRead::new(test.as_ref());
+    assert_eq!(0x1, read.read_bits(4).unwrap());
+
+    // The partial byte stays in the PeekRead
+    let mut read = read.into_inner();
+    assert_eq!(0x12, read.read_u8().unwrap());
+    assert_eq!(0x34, read.read_u8().unwrap());
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "std")]
+
+use peekbufread::PeekRead;
+use std::io::Read;
+
+#[test]
+fn peek_boxed_dyn_read() {
+    let test = b"hello, world";
+    let mut read: Box<PeekRead<dyn Read>> = Box::new(PeekRead::new(test.as_ref()));
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello", &buf);
+
+    let result = read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 7] = [0; 7];
+        read.read_exact(&mut buf).ok();
+        Err(())
+    });
+    assert!(result.is_err());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn peek_dyn_read_inner() {
+    let test = b"hello, world";
+    let mut inner: Box<dyn Read + Send> = Box::new(test.as_ref());
+    let mut read = PeekRead::new(&mut inner);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+}
+
+#[test]
+fn by_ref_with_leftover() {
+    let test = b"hello, world";
+    let mut inner = test.as_ref();
+    let mut leftover = Vec::new();
+
+    {
+        let mut read = PeekRead::by_ref_with(&mut inner, &mut leftover);
+        let mut buf: [u8; 12] = [0; 12];
+        let result = read.peek_exact(&mut buf);
+        assert!(result.is_ok());
+
+        let mut buf: [u8; 5] = [0; 5];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(b"hello", &buf);
+    }
+    assert_eq!(b", world", leftover.as_slice());
+    assert!(inner.is_empty());
+
+    let mut read = PeekRead::by_ref_with(&mut inner, &mut leftover);
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b", world", buf.as_slice());
+    read.finish();
+    assert!(leftover.is_empty());
+}
+
+#[test]
+fn by_ref_with_checkpoint_rollback() {
+    let test = b"hello, world";
+    let mut inner = test.as_ref();
+    let mut leftover = Vec::new();
+
+    let mut read = PeekRead::by_ref_with(&mut inner, &mut leftover);
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 7] = [0; 7];
+        read.read_exact(&mut buf).ok();
+        Err(())
+    })
+    .ok();
+    read.finish();
+    assert_eq!(b"hello, ", leftover.as_slice());
+
+    let mut buf = Vec::new();
+    let result = PeekRead::by_ref_with(&mut inner, &mut leftover).read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn by_ref_with_nothing_peeked() {
+    let test = b"hello, world";
+    let mut inner = test.as_ref();
+    let mut leftover = Vec::new();
+
+    PeekRead::by_ref_with(&mut inner, &mut leftover).finish();
+    assert!(leftover.is_empty());
+    assert_eq!(test, inner);
+}
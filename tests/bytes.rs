@@ -0,0 +1,130 @@
+#![cfg(all(feature = "bytes", feature = "std"))]
+
+use bytes::{Buf, BytesMut};
+use peekbufread::PeekRead;
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn peek_and_read_bytes() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let peeked = read.peek_bytes(5).unwrap();
+    let result = read.read_bytes(7);
+    assert_eq!(b"hello, ", &result.unwrap()[..]);
+    // Outlives the borrow and the consumption
+    assert_eq!(b"hello", &peeked[..]);
+
+    let result = read.read_bytes(6);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    let result = read.read_bytes(5);
+    assert_eq!(b"world", &result.unwrap()[..]);
+}
+
+#[test]
+fn read_bytes_zero_copy() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let buffered = read.peek_slice(12).unwrap().as_ptr();
+    let result = read.read_bytes(5);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(b"hello", &result[..]);
+    // Split off the buffer, not copied
+    assert_eq!(buffered, result.as_ptr());
+
+    let result = read.read_bytes(2).unwrap();
+    assert_eq!(buffered.wrapping_add(5), result.as_ptr());
+    assert_eq!(7, read.position());
+
+    let mut buf = String::new();
+    read.read_to_string(&mut buf).unwrap();
+    assert_eq!("world", buf);
+}
+
+#[test]
+fn checkpoint_read_bytes() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.checkpoint(|read| -> std::io::Result<()> {
+        let result = read.read_bytes(7);
+        assert_eq!(b"hello, ", &result.unwrap()[..]);
+        Err(ErrorKind::InvalidData.into())
+    });
+    assert!(result.is_err());
+
+    // Rolled back
+    let result = read.read_bytes(12);
+    assert_eq!(b"hello, world", &result.unwrap()[..]);
+}
+
+#[test]
+fn lookahead() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+    assert_eq!(0, read.lookahead().remaining());
+
+    let result = read.peek_slice(7);
+    assert!(result.is_ok());
+
+    let mut lookahead = read.lookahead();
+    assert_eq!(7, lookahead.remaining());
+    assert_eq!(b'h', lookahead.get_u8());
+    assert_eq!(u32::from_be_bytes(*b"ello"), lookahead.get_u32());
+    assert_eq!(b", ", lookahead.chunk());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b", world", buf.as_slice());
+}
+
+#[test]
+fn checkpoint_lookahead() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+    let result = read.peek_slice(12);
+    assert!(result.is_ok());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        read.lookahead().advance(7);
+        Err(())
+    })
+    .ok();
+
+    let result = read.read_bytes(12);
+    assert_eq!(test, &result.unwrap()[..]);
+}
+
+#[test]
+fn with_leftover() {
+    let test = b", world";
+    let leftover = BytesMut::from(&b"hello"[..]);
+    let mut read = PeekRead::new(test.as_ref()).with_leftover(leftover);
+
+    let result = read.peek_slice(7);
+    assert_eq!(b"hello, ", result.unwrap());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", buf.as_slice());
+}
+
+#[test]
+fn with_leftover_from_slice() {
+    let test = b", world";
+    let leftover = BytesMut::from(&b"hello"[..]);
+    let mut read = PeekRead::from_slice(test).with_leftover(leftover);
+
+    let result = read.peek_slice(7);
+    assert_eq!(b"hello, ", result.unwrap());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", buf.as_slice());
+}
@@ -0,0 +1,90 @@
+#![cfg(all(feature = "bytemuck", feature = "std"))]
+
+mod common;
+
+use bytemuck::{Pod, Zeroable};
+use common::Chunks;
+use peekbufread::PeekRead;
+use std::io::{ErrorKind, Read};
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+struct Record {
+    ts: u32,
+    len: u16,
+    kind: u16,
+}
+
+const RECORD: Record = Record {
+    ts: 0x01020304,
+    len: 2,
+    kind: 7,
+};
+
+fn record_bytes() -> Vec<u8> {
+    bytemuck::bytes_of(&RECORD).to_vec()
+}
+
+#[test]
+fn peek_as() {
+    let test = record_bytes();
+    let mut read = PeekRead::new(test.as_slice());
+
+    let result = read.peek_as::<Record>();
+    assert!(result.is_ok());
+    assert_eq!(&RECORD, result.unwrap());
+
+    // Nothing consumed
+    let result = read.read_as::<Record>();
+    assert!(result.is_ok());
+    assert_eq!(RECORD, result.unwrap());
+
+    let result = read.peek_as::<u8>();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn peek_as_misaligned() {
+    for offset in 1..4 {
+        let mut test = vec![0; offset];
+        test.extend(record_bytes());
+        test.extend(record_bytes());
+        let mut read = PeekRead::new(test.as_slice());
+
+        let mut buf = vec![0; offset];
+        read.read_exact(&mut buf).unwrap();
+
+        let result = read.peek_as::<Record>();
+        assert!(result.is_ok());
+        assert_eq!(&RECORD, result.unwrap());
+
+        assert_eq!(RECORD, read.read_as::<Record>().unwrap());
+        assert_eq!(&RECORD, read.peek_as::<Record>().unwrap());
+        assert_eq!(RECORD, read.read_as::<Record>().unwrap());
+    }
+}
+
+#[test]
+fn peek_as_partial_reads() {
+    let test = [record_bytes(), record_bytes()].concat();
+    let mut read = PeekRead::new(Chunks::new(&test, 3));
+
+    assert_eq!(&RECORD, read.peek_as::<Record>().unwrap());
+    assert_eq!(RECORD, read.read_as::<Record>().unwrap());
+    assert_eq!(0x01020304, read.read_as::<u32>().unwrap());
+    assert_eq!([2u16, 7], *read.peek_as::<[u16; 2]>().unwrap());
+}
+
+#[test]
+fn read_as_truncated() {
+    let test = record_bytes();
+    let mut read = PeekRead::new(Chunks::new(&test[..6], 4));
+
+    let result = read.read_as::<Record>();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    // Nothing consumed
+    let mut buf = Vec::new();
+    read.read_to_end(&mut buf).unwrap();
+    assert_eq!(&test[..6], buf);
+}
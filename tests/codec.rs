@@ -0,0 +1,95 @@
+#![cfg(feature = "tokio-util")]
+
+use bytes::BytesMut;
+use futures::StreamExt;
+use peekbufread::{PeekDecoder, PeekParser, PeekRead};
+use std::io::{ErrorKind, Read};
+use tokio::io::AsyncWriteExt;
+use tokio_util::codec::{Decoder, FramedRead};
+
+/// Frames with a length byte, peeks the length first
+struct LengthPrefixed;
+
+impl PeekParser for LengthPrefixed {
+    type Item = Vec<u8>;
+
+    fn parse<R: Read + ?Sized>(&mut self, read: &mut PeekRead<R>) -> std::io::Result<Vec<u8>> {
+        let mut len: [u8; 1] = [0; 1];
+        read.peek_exact(&mut len)?;
+        if len[0] == 0 {
+            return Err(ErrorKind::InvalidData.into());
+        }
+
+        read.checkpoint(|read| -> std::io::Result<Vec<u8>> {
+            let mut frame = vec![0; 1 + len[0] as usize];
+            read.read_exact(&mut frame)?;
+            frame.remove(0);
+            Ok(frame)
+        })
+    }
+}
+
+#[test]
+fn parse_sync() {
+    let test = b"\x05hello\x05world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = LengthPrefixed.parse(&mut read);
+    assert_eq!(b"hello", result.unwrap().as_slice());
+    let result = LengthPrefixed.parse(&mut read);
+    assert_eq!(b"world", result.unwrap().as_slice());
+    let result = LengthPrefixed.parse(&mut read);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn decode_incomplete() {
+    let mut decoder = PeekDecoder::new(LengthPrefixed);
+    let mut src = BytesMut::new();
+
+    let result = decoder.decode(&mut src);
+    assert!(matches!(result, Ok(None)));
+
+    src.extend_from_slice(b"\x05hel");
+    let result = decoder.decode(&mut src);
+    assert!(matches!(result, Ok(None)));
+    assert_eq!(b"\x05hel", &src[..]);
+
+    src.extend_from_slice(b"lo\x05w");
+    let result = decoder.decode(&mut src);
+    assert_eq!(b"hello", result.unwrap().unwrap().as_slice());
+    assert_eq!(b"\x05w", &src[..]);
+
+    let result = decoder.decode(&mut src);
+    assert!(matches!(result, Ok(None)));
+    assert_eq!(b"\x05w", &src[..]);
+}
+
+#[test]
+fn decode_invalid() {
+    let mut decoder = PeekDecoder::new(LengthPrefixed);
+    let mut src = BytesMut::from(&b"\x00"[..]);
+
+    let result = decoder.decode(&mut src);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+}
+
+#[tokio::test]
+async fn framed_read() {
+    let (mut client, server) = tokio::io::duplex(4);
+    let writer = tokio::spawn(async move {
+        client.write_all(b"\x05hello\x01,\x05world").await.unwrap();
+    });
+
+    let mut frames = FramedRead::new(server, PeekDecoder::new(LengthPrefixed));
+    let mut result = Vec::new();
+    while let Some(frame) = frames.next().await {
+        result.push(frame.unwrap());
+    }
+    writer.await.unwrap();
+
+    assert_eq!(
+        vec![b"hello".to_vec(), b",".to_vec(), b"world".to_vec()],
+        result
+    );
+}
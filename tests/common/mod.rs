@@ -0,0 +1,60 @@
+//! Readers shared by the integration tests
+#![allow(dead_code)]
+
+use peekbufread::io::{ErrorKind, Read, Result};
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+/// Returns its data in chunks of at most `chunk` bytes, like a device or a
+/// socket would
+pub struct Chunks<'a> {
+    steps: VecDeque<core::result::Result<&'a [u8], ErrorKind>>,
+    chunk: usize,
+    reads: Option<&'a Cell<usize>>,
+}
+
+impl<'a> Chunks<'a> {
+    pub fn new(data: &'a [u8], chunk: usize) -> Self {
+        Self {
+            steps: VecDeque::from([Ok(data)]),
+            chunk,
+            reads: None,
+        }
+    }
+
+    /// Replays a script of chunks and errors, like a non-blocking socket would
+    pub fn script(steps: Vec<core::result::Result<&'a [u8], ErrorKind>>) -> Self {
+        Self {
+            steps: steps.into(),
+            chunk: usize::MAX,
+            reads: None,
+        }
+    }
+
+    /// Counts the reads (syscalls for unbuffered readers) in `reads`
+    pub fn counted(mut self, reads: &'a Cell<usize>) -> Self {
+        self.reads = Some(reads);
+        self
+    }
+}
+
+impl Read for Chunks<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if let Some(reads) = self.reads {
+            reads.set(reads.get() + 1);
+        }
+
+        match self.steps.pop_front() {
+            Some(Ok(chunk)) => {
+                let len = chunk.len().min(buf.len()).min(self.chunk);
+                buf[..len].copy_from_slice(&chunk[..len]);
+                if len < chunk.len() {
+                    self.steps.push_front(Ok(&chunk[len..]));
+                }
+                Ok(len)
+            }
+            Some(Err(kind)) => Err(kind.into()),
+            None => Ok(0),
+        }
+    }
+}
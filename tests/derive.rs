@@ -0,0 +1,175 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+use peekbufread::{PeekDecode, PeekRead};
+use std::io::{ErrorKind, Read};
+
+#[derive(Debug, PartialEq, PeekDecode)]
+#[peek(magic = b"PK")]
+struct Header {
+    version: u16,
+    #[peek(le)]
+    flags: u32,
+    id: [u8; 2],
+    #[peek(len = u8)]
+    name: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, PeekDecode)]
+#[peek(le)]
+struct Points(#[peek(len = u16)] Vec<i16>, #[peek(be)] u16);
+
+#[derive(Debug, PartialEq, PeekDecode)]
+struct Blob {
+    #[peek(len = u64)]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, PeekDecode)]
+struct Versioned {
+    #[peek(magic = 0xcafe)]
+    magic: u16,
+    header: Header,
+}
+
+#[derive(Debug, PartialEq, PeekDecode)]
+#[peek(repr = u8)]
+enum Kind {
+    Empty = 0,
+    Text = 1,
+}
+
+#[derive(Debug, PartialEq, PeekDecode)]
+#[peek(repr = u16, le)]
+enum Message {
+    #[peek(tag = 1)]
+    Ping,
+    #[peek(tag = 2)]
+    Data { kind: Kind, value: u32 },
+    #[peek(tag = 0x100)]
+    Pair(u8, i8),
+}
+
+#[test]
+fn derive_struct() {
+    let test = b"PK\x00\x02\x01\x00\x00\x00ab\x03fooX";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Header::decode(&mut read);
+    assert!(result.is_ok());
+    assert_eq!(
+        Header {
+            version: 2,
+            flags: 1,
+            id: *b"ab",
+            name: b"foo".to_vec(),
+        },
+        result.unwrap()
+    );
+
+    let mut buf = Vec::new();
+    read.read_to_end(&mut buf).unwrap();
+    assert_eq!(b"X".as_ref(), buf);
+}
+
+#[test]
+fn derive_tuple_struct() {
+    let test = b"\x02\x00\xff\xff\x01\x00\x00\x01";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Points::decode(&mut read);
+    assert!(result.is_ok());
+    assert_eq!(Points(vec![-1, 1], 1), result.unwrap());
+}
+
+#[test]
+fn derive_nested() {
+    let test = b"\xca\xfePK\x00\x01\x00\x00\x00\x00ab\x00";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Versioned::decode(&mut read);
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().header.version);
+}
+
+#[test]
+fn derive_magic_mismatch() {
+    let test = b"PX\x00\x02\x01\x00\x00\x00ab\x00";
+    let mut read = PeekRead::new(test.as_ref());
+    let result = Header::decode(&mut read);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    let test = b"\xca\xffPK\x00\x01\x00\x00\x00\x00ab\x00";
+    let mut read = PeekRead::new(test.as_ref());
+    let result = Versioned::decode(&mut read);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    // Nothing consumed
+    assert_eq!(0, read.position());
+}
+
+#[test]
+fn derive_truncated() {
+    // The name is one byte short
+    let test = b"PK\x00\x02\x01\x00\x00\x00ab\x03fo";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Header::decode(&mut read);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    // Nothing consumed
+    let mut buf = Vec::new();
+    read.read_to_end(&mut buf).unwrap();
+    assert_eq!(test.as_ref(), buf);
+}
+
+#[test]
+fn derive_huge_len() {
+    // The length prefix is far beyond the end of the stream
+    let test = b"\xff\xff\xff\xff\xff\xff\xff\xf0ab";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Blob::decode(&mut read);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    assert_eq!(0, read.position());
+
+    let test = b"\x00\x00\x00\x00\xff\xff\xff\xffab";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Blob::decode(&mut read);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    assert_eq!(0, read.position());
+}
+
+#[test]
+fn derive_enum() {
+    let test = b"\x01\x00\x02\x00\x01\x2a\x00\x00\x00\x00\x01\x07\xff";
+    let mut read = PeekRead::new(test.as_ref());
+
+    assert_eq!(Message::Ping, Message::decode(&mut read).unwrap());
+    assert_eq!(
+        Message::Data {
+            kind: Kind::Text,
+            value: 42,
+        },
+        Message::decode(&mut read).unwrap()
+    );
+    assert_eq!(Message::Pair(7, -1), Message::decode(&mut read).unwrap());
+}
+
+#[test]
+fn derive_enum_unknown() {
+    let test = b"\x03\x00";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Message::decode(&mut read);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+    assert_eq!(0, read.position());
+
+    // Unknown discriminant of the nested enum
+    let test = b"\x02\x00\x05\x00\x00\x00\x00";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = Message::decode(&mut read);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+    assert_eq!(0, read.position());
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "std")]
+
+use peekbufread::{LineError, PeekRead};
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn peek_line() {
+    let test = b"EHLO example.com\r\nMAIL FROM:<a@b>\nQUIT";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_line(100);
+    assert_eq!(b"EHLO example.com\r\n", result.unwrap());
+
+    // Nothing consumed
+    let result = read.peek_line(100);
+    assert_eq!(b"EHLO example.com\r\n", result.unwrap());
+
+    let mut buf: [u8; 18] = [0; 18];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.peek_line(100);
+    assert_eq!(b"MAIL FROM:<a@b>\n", result.unwrap());
+
+    let mut buf: [u8; 16] = [0; 16];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.peek_line(100);
+    assert_eq!(b"QUIT", result.unwrap());
+
+    let mut buf: [u8; 4] = [0; 4];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.peek_line(100);
+    assert!(result.unwrap().is_empty());
+}
+
+#[test]
+fn peek_line_too_long() {
+    let test = b"hello\nworld";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_line(6);
+    assert_eq!(b"hello\n", result.unwrap());
+
+    let result = read.peek_line(5);
+    assert!(matches!(result, Err(LineError::TooLong { max_len: 5 })));
+
+    let err: std::io::Error = read.peek_line(5).unwrap_err().into();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+
+    // Only max_len bytes were buffered
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn peek_line_max_len_at_eof() {
+    // The last line is exactly max_len bytes long
+    let test = b"hello\nworld";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 6] = [0; 6];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.peek_line(5);
+    assert_eq!(b"world", result.unwrap());
+
+    let result = read.peek_line(4);
+    assert!(matches!(result, Err(LineError::TooLong { max_len: 4 })));
+}
+
+#[test]
+fn peek_line_zero_max_len() {
+    let test = b"a";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_line(0);
+    assert!(matches!(result, Err(LineError::TooLong { max_len: 0 })));
+
+    let mut buf: [u8; 1] = [0; 1];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    // End of the stream
+    let result = read.peek_line(0);
+    assert!(result.unwrap().is_empty());
+}
+
+#[test]
+fn lines_with_offsets() {
+    let test = b"+OK\r\n$5\r\nhello\r\n\nlast";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let lines: Vec<_> = read
+        .lines_with_offsets(100)
+        .map(|line| line.unwrap())
+        .collect();
+    assert_eq!(
+        vec![
+            (0, b"+OK".to_vec()),
+            (5, b"$5".to_vec()),
+            (9, b"hello".to_vec()),
+            (16, b"".to_vec()),
+            (17, b"last".to_vec()),
+        ],
+        lines
+    );
+    assert_eq!(21, read.position());
+}
+
+#[test]
+fn lines_with_offsets_too_long() {
+    let test = b"short\nthis line is too long\nshort";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut lines = read.lines_with_offsets(10);
+    assert_eq!((0, b"short".to_vec()), lines.next().unwrap().unwrap());
+    assert!(matches!(
+        lines.next(),
+        Some(Err(LineError::TooLong { max_len: 10 }))
+    ));
+    assert!(lines.next().is_none());
+    assert_eq!(6, read.position());
+}
+
+#[test]
+fn position() {
+    let test = "hello, world!".repeat(40).into_bytes();
+    let mut read = PeekRead::new(test.as_slice());
+    assert_eq!(0, read.position());
+
+    let mut buf = vec![0; 300];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, read.position());
+
+    let mut buf = vec![0; 200];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(200, read.position());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf = vec![0; 250];
+        read.read_exact(&mut buf).ok();
+        assert_eq!(450, read.position());
+        Err(())
+    })
+    .ok();
+    assert_eq!(200, read.position());
+
+    let mut buf = vec![0; 250];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(450, read.position());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(520, read.position());
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+
+use peekbufread::{Checkpoints, NoCheckpoints, PeekRead};
+use std::io::Read;
+
+#[test]
+fn default_mode_is_checkpoints() {
+    let test = b"hello, world";
+    let read: PeekRead<&[u8], Checkpoints> = PeekRead::new(test.as_ref());
+    let _: PeekRead<&[u8]> = read;
+}
+
+#[test]
+fn peek_read_without_checkpoints() {
+    let test = b"hello, world";
+    let mut read: PeekRead<&[u8], NoCheckpoints> = PeekRead::without_checkpoints(test.as_ref());
+
+    let mut buf: [u8; 6] = [0; 6];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..6], &buf);
+
+    let mut buf: [u8; 4] = [0; 4];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..4], &buf);
+
+    let mut buf: [u8; 8] = [0; 8];
+    let result = read.peek(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(8, result.unwrap());
+    assert_eq!(&test[4..], &buf);
+
+    let mut buf: [u8; 8] = [0; 8];
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(8, result.unwrap());
+    assert_eq!(&test[4..], &buf);
+
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+}
+
+#[test]
+fn read_ahead_without_checkpoints() {
+    let test = "hello, world!".repeat(40).into_bytes();
+    let mut read = PeekRead::without_checkpoints(test.as_slice()).with_read_ahead(64);
+
+    for i in 0..test.len() / 13 {
+        let mut buf: [u8; 13] = [0; 13];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[i * 13..(i + 1) * 13], &buf);
+    }
+}
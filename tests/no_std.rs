@@ -0,0 +1,68 @@
+#![cfg(not(feature = "std"))]
+
+mod common;
+
+use common::Chunks;
+use peekbufread::io::{Error, ErrorKind, Read};
+use peekbufread::{PeekRead, SlicePeekRead};
+
+#[test]
+fn peek_then_read() {
+    let mut read = PeekRead::new(Chunks::new(b"hello, world", 3));
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(3, result.unwrap());
+    assert_eq!(b"hel", &buf[..3]);
+
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello", &buf);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+
+    let result = read.peek_exact(&mut buf[..1]);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn checkpoint() {
+    let mut read = PeekRead::new(Chunks::new(b"hello, world", 4));
+
+    let result = read.checkpoint(|read| -> Result<(), Error> {
+        let mut buf: [u8; 7] = [0; 7];
+        read.read_exact(&mut buf)?;
+        assert_eq!(b"hello, ", &buf);
+        Err(ErrorKind::InvalidData.into())
+    });
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn slice_peek_read() {
+    let mut read = SlicePeekRead::new(b"hello, world");
+
+    let result = read.peek_slice(5);
+    assert_eq!(b"hello", result.unwrap());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 7] = [0; 7];
+        read.read_exact(&mut buf).ok();
+        Err(())
+    })
+    .ok();
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
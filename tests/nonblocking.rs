@@ -0,0 +1,217 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::Chunks;
+use peekbufread::PeekRead;
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn peek_exact_retries_interrupted() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"hello"),
+        Err(ErrorKind::Interrupted),
+        Ok(b", world"),
+    ]));
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn read_exact_retries_interrupted() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Err(ErrorKind::Interrupted),
+        Ok(b"hello"),
+        Err(ErrorKind::Interrupted),
+        Ok(b", world"),
+    ]));
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn peek_exact_would_block_keeps_lookahead() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"hello"),
+        Err(ErrorKind::WouldBlock),
+        Ok(b", world"),
+    ]));
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf);
+    assert_eq!(ErrorKind::WouldBlock, result.unwrap_err().kind());
+
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn read_exact_would_block_keeps_lookahead() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"hel"),
+        Ok(b"lo"),
+        Err(ErrorKind::WouldBlock),
+        Ok(b", world"),
+    ]));
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"he", &buf);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert_eq!(ErrorKind::WouldBlock, result.unwrap_err().kind());
+
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn read_exact_eof_keeps_lookahead() {
+    let mut read = PeekRead::new(Chunks::script(vec![Ok(b"hello")]));
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello", &buf);
+}
+
+#[test]
+fn read_would_block_returns_buffered() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"hello"),
+        Err(ErrorKind::WouldBlock),
+        Ok(b", world"),
+    ]));
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(5, result.unwrap());
+    assert_eq!(b"hello", &buf[..5]);
+
+    // Returned by the read after the buffered bytes
+    let result = read.read(&mut buf);
+    assert_eq!(ErrorKind::WouldBlock, result.unwrap_err().kind());
+
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(7, result.unwrap());
+    assert_eq!(b", world", &buf[..7]);
+}
+
+#[test]
+fn peek_error_after_buffered() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"abc"),
+        Err(ErrorKind::ConnectionReset),
+    ]));
+
+    let mut buf: [u8; 3] = [0; 3];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf: [u8; 8] = [0; 8];
+    let result = read.peek(&mut buf);
+    assert_eq!(ErrorKind::ConnectionReset, result.unwrap_err().kind());
+
+    // Nothing was consumed
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(3, result.unwrap());
+    assert_eq!(b"abc", &buf[..3]);
+}
+
+#[test]
+fn try_peek_exact() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"hello"),
+        Err(ErrorKind::WouldBlock),
+        Ok(b", wor"),
+        Err(ErrorKind::WouldBlock),
+        Ok(b"ld"),
+    ]));
+
+    let result = read.try_peek_exact(12);
+    assert!(matches!(result, Ok(None)));
+
+    let result = read.try_peek_exact(12);
+    assert!(matches!(result, Ok(None)));
+
+    let result = read.try_peek_exact(12);
+    assert_eq!(b"hello, world", result.unwrap().unwrap());
+
+    let result = read.try_peek_exact(13);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn checkpoint_would_block_rollback() {
+    let mut read = PeekRead::new(Chunks::script(vec![
+        Ok(b"hello"),
+        Err(ErrorKind::WouldBlock),
+        Ok(b", world"),
+    ]));
+
+    let result = read.checkpoint(|read| -> std::io::Result<()> {
+        let mut buf: [u8; 2] = [0; 2];
+        read.read_exact(&mut buf)?;
+        assert_eq!(b"he", &buf);
+
+        let mut buf: [u8; 10] = [0; 10];
+        read.read_exact(&mut buf)?;
+        Ok(())
+    });
+    assert_eq!(ErrorKind::WouldBlock, result.unwrap_err().kind());
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[test]
+fn read_after_large_peek() {
+    let test = "hello, world!".repeat(40).into_bytes();
+    let mut read = PeekRead::new(test.as_slice());
+
+    let mut buf = vec![0; 300];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut offset = 0;
+    for len in [100, 50, 10, 140, 220] {
+        let mut buf = vec![0; len];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[offset..offset + len], &buf[..]);
+        offset += len;
+    }
+}
@@ -0,0 +1,102 @@
+#![cfg(all(feature = "aho-corasick", feature = "std"))]
+
+mod common;
+
+use common::Chunks;
+use peekbufread::{Patterns, PeekRead};
+use std::io::Read;
+
+#[test]
+fn find_any_straddling_reads() {
+    let test = b"GET / HTTP/1.1\r\nHost: x\r\n\r\nbody--boundary";
+    let patterns = Patterns::new(["--boundary", "\r\n\r\n"]).unwrap();
+    let mut read = PeekRead::new(Chunks::new(test.as_ref(), 3));
+
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((1, 23)), result.unwrap());
+
+    // Nothing consumed
+    let mut buf: [u8; 27] = [0; 27];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((0, 4)), result.unwrap());
+}
+
+#[test]
+fn find_any_limit_and_eof() {
+    let test = b"hello, world";
+    let patterns = Patterns::new(["world", "moon"]).unwrap();
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.find_any(&patterns, 11);
+    assert_eq!(None, result.unwrap());
+
+    let result = read.find_any(&patterns, 12);
+    assert_eq!(Some((0, 7)), result.unwrap());
+
+    let patterns = Patterns::new(["moon"]).unwrap();
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(None, result.unwrap());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn find_any_leftmost() {
+    let test = b"abcdef";
+    let patterns = Patterns::new(["bcdef", "cd"]).unwrap();
+    let mut read = PeekRead::new(test.as_ref());
+
+    // Starts first, although "cd" ends first
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((0, 1)), result.unwrap());
+
+    // Must end within the limit
+    let result = read.find_any(&patterns, 5);
+    assert_eq!(Some((1, 2)), result.unwrap());
+}
+
+#[test]
+fn find_any_earliest_pattern() {
+    let test = b"--boundary--\r\n";
+    let patterns = Patterns::new(["--boundary--", "--boundary"]).unwrap();
+    let mut read = PeekRead::new(Chunks::new(test.as_ref(), 3));
+
+    // Keeps scanning past the match of "--boundary"
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((0, 0)), result.unwrap());
+
+    let patterns = Patterns::new(["--boundary", "--boundary--"]).unwrap();
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((0, 0)), result.unwrap());
+
+    let test = b"--boundary\r\n";
+    let patterns = Patterns::new(["--boundary--", "--boundary"]).unwrap();
+    let mut read = PeekRead::new(test.as_ref());
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((1, 0)), result.unwrap());
+}
+
+#[test]
+fn checkpoint_find_any() {
+    let test = b"one\r\ntwo\r\n";
+    let patterns = Patterns::new(["\r\n"]).unwrap();
+    let mut read = PeekRead::new(Chunks::new(test.as_ref(), 4));
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 5] = [0; 5];
+        read.read_exact(&mut buf).ok();
+        let result = read.find_any(&patterns, 100);
+        assert_eq!(Some((0, 3)), result.unwrap());
+        Err(())
+    })
+    .ok();
+
+    let result = read.find_any(&patterns, 100);
+    assert_eq!(Some((0, 3)), result.unwrap());
+}
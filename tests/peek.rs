@@ -1,3 +1,6 @@
+#![cfg(feature = "std")]
+#![allow(clippy::needless_return)]
+
 use peekbufread::PeekRead;
 use std::io::Read;
 
@@ -253,7 +256,6 @@ fn peek_read_peek() {
     assert_eq!(&test[6..12], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_read_ok() {
     let test = b"hello, world";
@@ -277,7 +279,6 @@ fn checkpoint_read_ok() {
     assert_eq!(&test[5..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_read_err() {
     let test = b"hello, world";
@@ -301,7 +302,6 @@ fn checkpoint_read_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_read_partial_err() {
     let test = b"hello, world";
@@ -325,7 +325,6 @@ fn checkpoint_read_partial_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_peek_err() {
     let test = b"hello, world";
@@ -349,7 +348,6 @@ fn checkpoint_peek_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_peek_partial_err() {
     let test = b"hello, world";
@@ -373,7 +371,6 @@ fn checkpoint_peek_partial_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn read_partial_checkpoint_read_err() {
     let test = b"hello, world";
@@ -403,7 +400,6 @@ fn read_partial_checkpoint_read_err() {
     assert_eq!(&test[6..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn read_partial_checkpoint_read_peek_err() {
     let test = b"hello, world";
@@ -433,7 +429,6 @@ fn read_partial_checkpoint_read_peek_err() {
     assert_eq!(&test[6..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_ok_in_checkpoint_err() {
     let test = b"hello, world";
@@ -468,7 +463,6 @@ fn checkpoint_ok_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn partial_peek_checkpoint_ok_in_checkpoint_err() {
     let test = b"hello, world";
@@ -509,7 +503,6 @@ fn partial_peek_checkpoint_ok_in_checkpoint_err() {
     assert_eq!(&test[4..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_ok_after_in_checkpoint_err() {
     let test = b"hello, world";
@@ -544,7 +537,6 @@ fn checkpoint_ok_after_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn partial_peek_checkpoint_err_in_checkpoint_err() {
     let test = b"hello, world";
@@ -584,7 +576,6 @@ fn partial_peek_checkpoint_err_in_checkpoint_err() {
     assert_eq!(&test[4..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_err_in_checkpoint_err() {
     let test = b"hello, world";
@@ -619,7 +610,6 @@ fn checkpoint_err_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_err_equal_in_checkpoint_err() {
     let test = b"hello, world";
@@ -654,7 +644,6 @@ fn checkpoint_err_equal_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_err_after_in_checkpoint_err() {
     let test = b"hello, world";
@@ -1,3 +1,6 @@
+#![cfg(feature = "std")]
+#![allow(clippy::needless_return)]
+
 use peekbufread::PeekRead;
 use std::io::Read;
 
@@ -271,7 +274,6 @@ fn peek_read_peek() {
     assert_eq!(&test[6..12], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_read_ok() {
     let test = b"hello, world";
@@ -293,7 +295,6 @@ fn checkpoint_read_ok() {
     assert_eq!(&test[5..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_read_err() {
     let test = b"hello, world";
@@ -315,7 +316,6 @@ fn checkpoint_read_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_read_partial_err() {
     let test = b"hello, world";
@@ -337,7 +337,6 @@ fn checkpoint_read_partial_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_peek_exact_err() {
     let test = b"hello, world";
@@ -359,7 +358,6 @@ fn checkpoint_peek_exact_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_peek_exact_partial_err() {
     let test = b"hello, world";
@@ -381,7 +379,6 @@ fn checkpoint_peek_exact_partial_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn read_partial_checkpoint_read_err() {
     let test = b"hello, world";
@@ -408,7 +405,6 @@ fn read_partial_checkpoint_read_err() {
     assert_eq!(&test[6..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn read_partial_checkpoint_read_peek_exact_err() {
     let test = b"hello, world";
@@ -435,7 +431,6 @@ fn read_partial_checkpoint_read_peek_exact_err() {
     assert_eq!(&test[6..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_ok_in_checkpoint_err() {
     let test = b"hello, world";
@@ -467,7 +462,6 @@ fn checkpoint_ok_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn partial_peek_exact_checkpoint_ok_in_checkpoint_err() {
     let test = b"hello, world";
@@ -504,7 +498,6 @@ fn partial_peek_exact_checkpoint_ok_in_checkpoint_err() {
     assert_eq!(&test[4..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_ok_after_in_checkpoint_err() {
     let test = b"hello, world";
@@ -536,7 +529,6 @@ fn checkpoint_ok_after_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn partial_peek_exact_checkpoint_err_in_checkpoint_err() {
     let test = b"hello, world";
@@ -572,7 +564,6 @@ fn partial_peek_exact_checkpoint_err_in_checkpoint_err() {
     assert_eq!(&test[4..], &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_err_in_checkpoint_err() {
     let test = b"hello, world";
@@ -604,7 +595,6 @@ fn checkpoint_err_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_err_equal_in_checkpoint_err() {
     let test = b"hello, world";
@@ -636,7 +626,6 @@ fn checkpoint_err_equal_in_checkpoint_err() {
     assert_eq!(test, &buf);
 }
 
-#[cfg(feature = "checkpoint")]
 #[test]
 fn checkpoint_err_after_in_checkpoint_err() {
     let test = b"hello, world";
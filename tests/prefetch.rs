@@ -0,0 +1,205 @@
+#![cfg(feature = "std")]
+
+use peekbufread::{PeekRead, Prefetch};
+use std::io::{Cursor, ErrorKind, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn prefetch_peek_read() {
+    let test = "hello, world!".repeat(100).into_bytes();
+    let mut read = PeekRead::new(Prefetch::with_high_water_mark(
+        Cursor::new(test.clone()),
+        7,
+        2,
+    ));
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..12], &buf);
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf);
+}
+
+#[test]
+fn prefetch_checkpoint_err() {
+    let test = b"hello, world".to_vec();
+    let mut read = PeekRead::prefetch(Cursor::new(test.clone()));
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 12] = [0; 12];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(test, &buf);
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+}
+
+#[test]
+fn prefetch_error() {
+    /// Fails after the first read
+    struct FailSecond(bool);
+
+    impl Read for FailSecond {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0 {
+                return Err(std::io::Error::other("broken pipe"));
+            }
+
+            self.0 = true;
+            buf[..5].copy_from_slice(b"hello");
+            Ok(5)
+        }
+    }
+
+    let mut read = PeekRead::prefetch(FailSecond(false));
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello", &buf);
+
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_err());
+    assert_eq!("broken pipe", result.unwrap_err().to_string());
+}
+
+#[test]
+fn prefetch_error_stops() {
+    /// Always fails and counts its reads
+    struct AlwaysFail(Arc<AtomicUsize>);
+
+    impl Read for AlwaysFail {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Err(std::io::Error::other("broken pipe"))
+        }
+    }
+
+    let reads = Arc::new(AtomicUsize::new(0));
+    let mut read = Prefetch::with_high_water_mark(AlwaysFail(reads.clone()), 4, 4);
+
+    let mut buf: [u8; 4] = [0; 4];
+    let result = read.read(&mut buf);
+    assert!(result.is_err());
+    assert_eq!("broken pipe", result.unwrap_err().to_string());
+
+    // The thread has exited, but the error is returned again
+    let result = read.read(&mut buf);
+    assert!(result.is_err());
+    assert_eq!("broken pipe", result.unwrap_err().to_string());
+    assert_eq!(1, reads.load(Ordering::SeqCst));
+}
+
+#[test]
+fn prefetch_error_after_buffered() {
+    /// Returns "abc", then fails
+    struct Reset(bool);
+
+    impl Read for Reset {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0 {
+                return Err(ErrorKind::ConnectionReset.into());
+            }
+
+            self.0 = true;
+            buf[..3].copy_from_slice(b"abc");
+            Ok(3)
+        }
+    }
+
+    let mut read = PeekRead::prefetch(Reset(false));
+
+    let mut buf: [u8; 3] = [0; 3];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf: [u8; 8] = [0; 8];
+    let result = read.peek(&mut buf);
+    assert_eq!(ErrorKind::ConnectionReset, result.unwrap_err().kind());
+
+    // The buffered bytes, then the error
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(3, result.unwrap());
+    assert_eq!(b"abc", &buf[..3]);
+
+    let result = read.read(&mut buf);
+    assert_eq!(ErrorKind::ConnectionReset, result.unwrap_err().kind());
+
+    let mut read = PeekRead::prefetch(Reset(false));
+    let result = read.peek_exact(&mut [0; 3]);
+    assert!(result.is_ok());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert_eq!(ErrorKind::ConnectionReset, result.unwrap_err().kind());
+    assert_eq!(b"abc", buf.as_slice());
+}
+
+#[test]
+fn prefetch_panic() {
+    struct Panic;
+
+    impl Read for Panic {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            panic!("decompressor panicked");
+        }
+    }
+
+    let mut read = PeekRead::prefetch(Panic);
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek(&mut buf);
+    assert!(result.is_err());
+    assert_eq!(ErrorKind::Other, result.unwrap_err().kind());
+}
+
+/// Endless reader, which counts its reads
+struct Endless(Arc<AtomicUsize>);
+
+impl Read for Endless {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        buf.fill(b'a');
+        Ok(buf.len())
+    }
+}
+
+#[test]
+fn prefetch_high_water_mark() {
+    let reads = Arc::new(AtomicUsize::new(0));
+    let mut read = PeekRead::new(Prefetch::with_high_water_mark(Endless(reads.clone()), 4, 3));
+
+    let mut buf: [u8; 4] = [0; 4];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    // Consumed + queued + waiting for space in the queue
+    assert!(reads.load(Ordering::SeqCst) <= 1 + 3 + 1);
+}
+
+#[test]
+fn prefetch_drop() {
+    let reads = Arc::new(AtomicUsize::new(0));
+    let read = PeekRead::new(Prefetch::with_high_water_mark(Endless(reads.clone()), 4, 1));
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    drop(read);
+
+    let after_drop = reads.load(Ordering::SeqCst);
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    assert_eq!(after_drop, reads.load(Ordering::SeqCst));
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::Chunks;
+use peekbufread::PeekRead;
+use std::cell::Cell;
+use std::io::Read;
+
+fn test_data() -> Vec<u8> {
+    (0..1024).map(|i| i as u8).collect()
+}
+
+#[test]
+fn read_ahead_peek_read() {
+    let test = test_data();
+    let reads = Cell::new(0);
+    let mut read =
+        PeekRead::new(Chunks::new(&test, usize::MAX).counted(&reads)).with_read_ahead(256);
+
+    for i in 0..test.len() / 16 {
+        let mut buf: [u8; 4] = [0; 4];
+        let result = read.peek_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[i * 16..i * 16 + 4], &buf);
+
+        let mut buf: [u8; 16] = [0; 16];
+        let result = read.read(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(16, result.unwrap());
+        assert_eq!(&test[i * 16..(i + 1) * 16], &buf);
+    }
+
+    assert_eq!(4, reads.get());
+
+    let mut buf: [u8; 16] = [0; 16];
+    let result = read.peek(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+}
+
+#[test]
+fn read_ahead_read_exact() {
+    let test = test_data();
+    let reads = Cell::new(0);
+    let mut read =
+        PeekRead::new(Chunks::new(&test, usize::MAX).counted(&reads)).with_read_ahead(256);
+
+    for i in 0..test.len() / 8 {
+        let mut buf: [u8; 8] = [0; 8];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[i * 8..(i + 1) * 8], &buf);
+    }
+
+    assert_eq!(4, reads.get());
+}
+
+#[test]
+fn read_ahead_large_read_bypasses_buffer() {
+    let test = test_data();
+    let reads = Cell::new(0);
+    let mut read =
+        PeekRead::new(Chunks::new(&test, usize::MAX).counted(&reads)).with_read_ahead(64);
+
+    let mut buf: [u8; 4] = [0; 4];
+    let result = read.peek(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(4, result.unwrap());
+    assert_eq!(1, reads.get());
+
+    let mut buf = vec![0; 512];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..512], buf.as_slice());
+    assert_eq!(2, reads.get());
+
+    let mut buf = vec![0; 512];
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(512, result.unwrap());
+    assert_eq!(&test[512..], buf.as_slice());
+    assert_eq!(3, reads.get());
+}
+
+#[test]
+fn adaptive_read_ahead_grows() {
+    let test = test_data();
+    let reads = Cell::new(0);
+    let mut read = PeekRead::new(Chunks::new(&test, usize::MAX).counted(&reads))
+        .with_adaptive_read_ahead(16, 512);
+
+    for i in 0..test.len() / 8 {
+        let mut buf: [u8; 8] = [0; 8];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[i * 8..(i + 1) * 8], &buf);
+    }
+
+    // 16 + 32 + 64 + 128 + 256 + 512 + 512 >= 1024
+    assert_eq!(7, reads.get());
+}
+
+#[test]
+#[should_panic(expected = "min must not be zero")]
+fn adaptive_read_ahead_min_zero() {
+    let test = test_data();
+    PeekRead::new(test.as_slice()).with_adaptive_read_ahead(0, 512);
+}
+
+#[test]
+fn adaptive_read_ahead_shrinks() {
+    /// Returns at most `limit` bytes per read (like a socket)
+    struct Limit<'a> {
+        read: &'a [u8],
+        limit: &'a Cell<usize>,
+        requested: &'a Cell<usize>,
+    }
+
+    impl Read for Limit<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.requested.set(buf.len());
+            let len = buf.len().min(self.limit.get());
+            self.read.read(&mut buf[..len])
+        }
+    }
+
+    let test = test_data();
+    let limit = Cell::new(usize::MAX);
+    let requested = Cell::new(0);
+    let mut read = PeekRead::new(Limit {
+        read: &test,
+        limit: &limit,
+        requested: &requested,
+    })
+    .with_adaptive_read_ahead(8, 128);
+
+    let mut buf: [u8; 4] = [0; 4];
+    for i in 0..64 {
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[i * 4..(i + 1) * 4], &buf);
+    }
+    assert_eq!(128, requested.get());
+
+    limit.set(4);
+    for i in 64..128 {
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[i * 4..(i + 1) * 4], &buf);
+    }
+    assert_eq!(8, requested.get());
+}
+
+#[test]
+fn read_ahead_checkpoint_err() {
+    let test = test_data();
+    let mut read = PeekRead::new(test.as_slice()).with_read_ahead(64);
+
+    let mut buf: [u8; 10] = [0; 10];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 60] = [0; 60];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[10..70], &buf);
+
+        let mut buf: [u8; 8] = [0; 8];
+        let result = read.read(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(8, result.unwrap());
+        assert_eq!(&test[70..78], &buf);
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf = vec![0; 1014];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[10..], buf.as_slice());
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "read_buf")]
+#![feature(core_io_borrowed_buf, read_buf)]
+
+use peekbufread::PeekRead;
+use std::io::{BorrowedBuf, Read};
+use std::mem::MaybeUninit;
+
+#[test]
+fn read_buf() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [MaybeUninit<u8>; 12] = [MaybeUninit::uninit(); 12];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.read_buf(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(test, buf.filled());
+}
+
+#[test]
+fn partial_peek_read_buf() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf: [MaybeUninit<u8>; 12] = [MaybeUninit::uninit(); 12];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.read_buf(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(test, buf.filled());
+}
+
+#[test]
+fn peek_buf() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [MaybeUninit<u8>; 5] = [MaybeUninit::uninit(); 5];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.peek_buf(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(&test[..5], buf.filled());
+
+    let mut buf: [MaybeUninit<u8>; 12] = [MaybeUninit::uninit(); 12];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.peek_buf(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(test, buf.filled());
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+}
+
+#[test]
+fn read_buf_exact() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf: [MaybeUninit<u8>; 12] = [MaybeUninit::uninit(); 12];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.read_buf_exact(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(test, buf.filled());
+
+    let mut buf: [MaybeUninit<u8>; 1] = [MaybeUninit::uninit(); 1];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.read_buf_exact(buf.unfilled());
+    assert!(result.is_err());
+}
+
+#[test]
+fn peek_exact_large() {
+    const LEN: usize = 13 * 128;
+    let test = "hello, world!".repeat(LEN / 13).into_bytes();
+    let mut read = PeekRead::new(test.as_slice());
+
+    let mut buf = vec![0; LEN];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf);
+
+    let mut buf: Vec<MaybeUninit<u8>> = vec![MaybeUninit::uninit(); LEN];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.read_buf_exact(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(test, buf.filled());
+}
+
+#[test]
+fn checkpoint_read_buf_err() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [MaybeUninit<u8>; 12] = [MaybeUninit::uninit(); 12];
+        let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+        let result = read.read_buf_exact(buf.unfilled());
+        assert!(result.is_ok());
+        assert_eq!(test, buf.filled());
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf: [MaybeUninit<u8>; 12] = [MaybeUninit::uninit(); 12];
+    let mut buf: BorrowedBuf<'_> = buf.as_mut_slice().into();
+    let result = read.read_buf_exact(buf.unfilled());
+    assert!(result.is_ok());
+    assert_eq!(test, buf.filled());
+}
@@ -0,0 +1,204 @@
+#![cfg(feature = "std")]
+
+use peekbufread::PeekRead;
+use std::io::Read;
+
+#[test]
+fn read_to_end() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn partial_peek_read_to_end() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf = b"> ".to_vec();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(b"> hello, world", buf.as_slice());
+
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+}
+
+#[test]
+fn partial_peek_read_to_string() {
+    let test = "hello, wörld";
+    let mut read = PeekRead::new(test.as_bytes());
+
+    // Splits the 'ö' between buffer and inner reader
+    let mut buf: [u8; 9] = [0; 9];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut buf = String::new();
+    let result = read.read_to_string(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(13, result.unwrap());
+    assert_eq!(test, buf);
+}
+
+#[test]
+fn read_to_string_invalid() {
+    let test = b"hello, \xffworld";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf = String::from("> ");
+    let result = read.read_to_string(&mut buf);
+    assert!(result.is_err());
+    assert_eq!(std::io::ErrorKind::InvalidData, result.unwrap_err().kind());
+    assert_eq!("> ", buf);
+}
+
+#[test]
+fn checkpoint_read_to_end_err() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 3] = [0; 3];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf = Vec::new();
+        let result = read.read_to_end(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(test, buf.as_slice());
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf = String::new();
+    let result = read.read_to_string(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!("hello, world", buf);
+}
+
+#[test]
+fn copy_to() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut out = Vec::new();
+    let result = read.copy_to(&mut out);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(test, out.as_slice());
+
+    let result = read.copy_to(&mut out);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+}
+
+/// Accepts `capacity` bytes, fails afterwards
+struct FailingWriter {
+    written: Vec<u8>,
+    capacity: usize,
+}
+
+impl std::io::Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.written.len() >= self.capacity {
+            return Err(std::io::Error::other("writer is full"));
+        }
+
+        let len = buf.len().min(self.capacity - self.written.len()).min(3);
+        self.written.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn copy_to_writer_err() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut out = FailingWriter {
+        written: Vec::new(),
+        capacity: 7,
+    };
+    let result = read.copy_to(&mut out);
+    assert!(result.is_err());
+    assert_eq!(b"hello, ", out.written.as_slice());
+
+    // Exactly the written bytes are consumed
+    assert_eq!(7, read.position());
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"world", buf.as_slice());
+}
+
+#[test]
+fn copy_to_writer_err_unbuffered() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut out = FailingWriter {
+        written: Vec::new(),
+        capacity: 7,
+    };
+    let result = read.copy_to(&mut out);
+    assert!(result.is_err());
+    assert_eq!(b"hello, ", out.written.as_slice());
+
+    // The bytes, which the writer didn't take, are buffered
+    assert_eq!(7, read.position());
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"world", buf.as_slice());
+}
+
+#[test]
+fn checkpoint_copy_to_err() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut out = Vec::new();
+        let result = read.copy_to(&mut out);
+        assert!(result.is_ok());
+        assert_eq!(7, result.unwrap());
+        assert_eq!(b", world", out.as_slice());
+
+        Err(())
+    })
+    .ok();
+
+    let mut out = Vec::new();
+    let result = read.copy_to(&mut out);
+    assert!(result.is_ok());
+    assert_eq!(b", world", out.as_slice());
+}
@@ -0,0 +1,123 @@
+#![cfg(all(feature = "regex", feature = "std"))]
+
+mod common;
+
+use common::Chunks;
+use peekbufread::{PeekRead, Regex};
+use std::cell::Cell;
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn peek_match() {
+    let test = b"12345 + foo";
+    let number = Regex::new("[0-9]+").unwrap();
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_match(&number, 100);
+    assert_eq!(Some(5), result.unwrap());
+
+    // Nothing consumed
+    let result = read.peek_match(&number, 100);
+    assert_eq!(Some(5), result.unwrap());
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.peek_match(&number, 100);
+    assert_eq!(None, result.unwrap());
+}
+
+#[test]
+fn peek_match_reads_lazily() {
+    let test = b"abc123";
+    let reads = Cell::new(0);
+    let word = Regex::new("[a-z]+").unwrap();
+    let mut read = PeekRead::new(Chunks::new(test.as_ref(), 1).counted(&reads));
+
+    let result = read.peek_match(&word, 100);
+    assert_eq!(Some(3), result.unwrap());
+    // The DFA needs one more byte to report the match and one to give up
+    assert_eq!(5, reads.get());
+}
+
+#[test]
+fn peek_match_eof() {
+    let test = b"foobar";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_match(&Regex::new("foo(bar)?$").unwrap(), 100);
+    assert_eq!(Some(6), result.unwrap());
+
+    let result = read.peek_match(&Regex::new("").unwrap(), 100);
+    assert_eq!(Some(0), result.unwrap());
+
+    let result = read.peek_match(&Regex::new("foobarbaz").unwrap(), 100);
+    assert_eq!(None, result.unwrap());
+}
+
+#[test]
+fn peek_match_scan_limit() {
+    let test = b"aaaaaaaaaa";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_match(&Regex::new("a+").unwrap(), 5);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    let result = read.peek_match(&Regex::new("a{3}").unwrap(), 5);
+    assert_eq!(Some(3), result.unwrap());
+}
+
+#[test]
+fn peek_match_scan_limit_boundary() {
+    let test = b"abcdef";
+    let mut read = PeekRead::new(test.as_ref());
+
+    // The limit is the length of the match
+    let result = read.peek_match(&Regex::new("ab").unwrap(), 2);
+    assert_eq!(Some(2), result.unwrap());
+
+    let result = read.peek_match(&Regex::new("abc").unwrap(), 2);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    let test = b"abc def";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_match(&Regex::new("[a-z]+").unwrap(), 3);
+    assert_eq!(Some(3), result.unwrap());
+
+    let result = read.peek_match(&Regex::new("[a-z]+").unwrap(), 2);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    // Ends with the stream
+    let test = b"aaa";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_match(&Regex::new("a+").unwrap(), 3);
+    assert_eq!(Some(3), result.unwrap());
+
+    let result = read.peek_match(&Regex::new("a+").unwrap(), 2);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+}
+
+#[test]
+fn consume_match_tokenizer() {
+    let test = b"let x = 42;";
+    let ident = Regex::new("[a-z]+").unwrap();
+    let number = Regex::new("[0-9]+").unwrap();
+    let space = Regex::new(r"[ =;]+").unwrap();
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut tokens = Vec::new();
+    loop {
+        if let Some(len) = read.consume_match(&ident, 100).unwrap() {
+            tokens.push(("ident", len));
+        } else if let Some(len) = read.consume_match(&number, 100).unwrap() {
+            tokens.push(("number", len));
+        } else if read.consume_match(&space, 100).unwrap().is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(vec![("ident", 3), ("ident", 1), ("number", 2)], tokens);
+}
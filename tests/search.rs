@@ -0,0 +1,136 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::Chunks;
+use peekbufread::PeekRead;
+use std::cell::Cell;
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn peek_until() {
+    let test = b"hello\nworld\n";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_until(b'\n', 100);
+    assert_eq!(b"hello\n", result.unwrap());
+
+    // Nothing consumed
+    let result = read.peek_until(b'\n', 100);
+    assert_eq!(b"hello\n", result.unwrap());
+
+    let mut buf: [u8; 6] = [0; 6];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.peek_until(b'\n', 100);
+    assert_eq!(b"world\n", result.unwrap());
+}
+
+#[test]
+fn peek_until_limit_and_eof() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.peek_until(b'\n', 5);
+    assert_eq!(b"hello", result.unwrap());
+
+    let result = read.peek_until(b'\n', 100);
+    assert_eq!(b"hello, world", result.unwrap());
+
+    let result = read.peek_until(b'o', 4);
+    assert_eq!(b"hell", result.unwrap());
+
+    let result = read.peek_until(b'o', 5);
+    assert_eq!(b"hello", result.unwrap());
+}
+
+#[test]
+fn position_of_reads_lazily() {
+    let test = b"hello, world";
+    let reads = Cell::new(0);
+    let mut read = PeekRead::new(Chunks::new(test.as_ref(), 3).counted(&reads));
+
+    let result = read.position_of(b',', 100);
+    assert_eq!(Some(5), result.unwrap());
+    assert_eq!(2, reads.get());
+
+    let result = read.position_of(b'w', 7);
+    assert_eq!(None, result.unwrap());
+    assert_eq!(3, reads.get());
+
+    let result = read.position_of(b'w', 8);
+    assert_eq!(Some(7), result.unwrap());
+    assert_eq!(4, reads.get());
+
+    // Already buffered
+    let result = read.position_of(b'o', 8);
+    assert_eq!(Some(4), result.unwrap());
+    assert_eq!(4, reads.get());
+
+    let result = read.position_of(b'x', 100);
+    assert_eq!(None, result.unwrap());
+    assert_eq!(7, reads.get());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn skip_until() {
+    let test = "a".repeat(20000) + "\nhello";
+    let reads = Cell::new(0);
+    let mut read = PeekRead::new(Chunks::new(test.as_bytes(), 1000).counted(&reads));
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let result = read.skip_until(b'\n', 30000);
+    assert_eq!(20001, result.unwrap());
+
+    let mut buf = String::new();
+    let result = read.read_to_string(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!("hello", buf);
+
+    let result = read.skip_until(b'\n', 30000);
+    assert_eq!(0, result.unwrap());
+}
+
+#[test]
+fn skip_until_limit() {
+    let test = "a".repeat(20000) + "\nhello";
+    let reads = Cell::new(0);
+    let mut read = PeekRead::new(Chunks::new(test.as_bytes(), 1000).counted(&reads));
+
+    let result = read.skip_until(b'\n', 10000);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+    // Read only as far as the limit
+    assert_eq!(10000, read.position());
+    assert_eq!(10, reads.get());
+
+    // The delimiter is the last byte within the limit
+    let result = read.skip_until(b'\n', 10001);
+    assert_eq!(10001, result.unwrap());
+}
+
+#[test]
+fn checkpoint_skip_until() {
+    let test = b"hello\nworld";
+    let mut read = PeekRead::new(test.as_ref());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let result = read.skip_until(b'\n', 100);
+        assert_eq!(6, result.unwrap());
+        let result = read.skip_until(b'\n', 100);
+        assert_eq!(5, result.unwrap());
+        Err(())
+    })
+    .ok();
+
+    let result = read.peek_until(b'\n', 100);
+    assert_eq!(b"hello\n", result.unwrap());
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "std")]
+
+use peekbufread::{PeekRead, SlicePeekRead};
+use std::io::{BufRead, Cursor, Read};
+
+#[test]
+fn peek_read() {
+    let test = b"hello, world";
+    let mut read = SlicePeekRead::new(test);
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(5, result.unwrap());
+    assert_eq!(&test[..5], &buf);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+
+    let mut buf: [u8; 6] = [0; 6];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..6], &buf);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(6, result.unwrap());
+    assert_eq!(&test[6..], &buf[..6]);
+
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_err());
+}
+
+#[test]
+fn peek_slice_borrows() {
+    let test = b"hello, world";
+    let mut read = SlicePeekRead::new(test);
+
+    let peeked = read.peek_slice(5).unwrap();
+    let mut buf: [u8; 7] = [0; 7];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    // The peeked slice outlives the following reads
+    assert_eq!(b"hello", peeked);
+    assert!(std::ptr::eq(peeked.as_ptr(), test.as_ptr()));
+
+    let result = read.try_peek_exact(5);
+    assert_eq!(b"world", result.unwrap().unwrap());
+}
+
+#[test]
+fn read_exact_eof_untouched() {
+    let test = b"hello, world";
+    let mut read = SlicePeekRead::new(test);
+
+    let mut buf: [u8; 13] = [0; 13];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_err());
+    assert_eq!(0, read.position());
+}
+
+#[test]
+fn from_cursor() {
+    let test = b"hello, world";
+    let mut cursor = Cursor::new(test.as_ref());
+    cursor.set_position(7);
+
+    let mut read = SlicePeekRead::from(cursor);
+    let mut buf = String::new();
+    let result = read.read_to_string(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!("world", buf);
+}
+
+#[test]
+fn read_until() {
+    let test = b"hello, world";
+    let mut read = SlicePeekRead::new(test);
+
+    let mut buf = Vec::new();
+    let result = read.read_until(b',', &mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..6], &buf);
+    assert_eq!(b" world", read.remaining());
+}
+
+#[test]
+fn checkpoint_ok_in_checkpoint_err() {
+    let test = b"hello, world";
+    let mut read = SlicePeekRead::new(test);
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        read.checkpoint(|read| -> Result<(), ()> {
+            let mut buf: [u8; 6] = [0; 6];
+            let result = read.read_exact(&mut buf);
+            assert!(result.is_ok());
+            assert_eq!(&test[..6], &buf);
+
+            Ok(())
+        })
+        .ok();
+
+        let mut buf: [u8; 6] = [0; 6];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[6..], &buf);
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+}
+
+#[test]
+fn peek_read_from_slice() {
+    let test = b"hello, world";
+    let mut read = PeekRead::from_slice(test);
+
+    let result = read.peek_slice(5);
+    assert!(result.is_ok());
+    assert_eq!(b"hello", result.unwrap());
+    // Nothing is copied
+    assert!(std::ptr::eq(
+        read.peek_slice(5).unwrap().as_ptr(),
+        test.as_ptr()
+    ));
+
+    let mut buf: [u8; 13] = [0; 13];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_err());
+    assert_eq!(0, read.position());
+
+    let mut buf: [u8; 7] = [0; 7];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(&test[..7], &buf);
+
+    let result = read.fill_buf();
+    assert!(result.is_ok());
+    assert_eq!(b"world", result.unwrap());
+    read.consume(2);
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"rld", buf.as_slice());
+    assert_eq!(12, read.position());
+}
+
+#[test]
+fn checkpoint_from_slice() {
+    let test = b"hello, world";
+    let mut read = PeekRead::from_slice(test);
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut buf: [u8; 6] = [0; 6];
+        let result = read.read_exact(&mut buf);
+        assert!(result.is_ok());
+        assert_eq!(&test[..6], &buf);
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(test, &buf);
+
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+}
+
+#[test]
+fn peek_read_from_cursor() {
+    let test = b"hello, world";
+    let mut cursor = Cursor::new(test.as_ref());
+    cursor.set_position(7);
+
+    let mut read = PeekRead::from_cursor(cursor);
+    let result = read.peek_slice(5);
+    assert!(result.is_ok());
+    assert_eq!(b"world", result.unwrap());
+    assert_eq!(0, read.position());
+
+    let mut buf = String::new();
+    let result = read.read_to_string(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!("world", buf);
+}
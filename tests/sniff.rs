@@ -0,0 +1,146 @@
+#![cfg(feature = "std")]
+
+use peekbufread::{Format, PeekRead, Sniffer};
+use std::io::Read;
+
+fn sniff(test: &[u8]) -> Option<Format> {
+    let mut read = PeekRead::new(test);
+    let result = read.sniff();
+    assert!(result.is_ok());
+    result.unwrap()
+}
+
+#[test]
+fn sniff_binary() {
+    assert_eq!(Some(Format::Gzip), sniff(b"\x1f\x8b\x08\x00"));
+    assert_eq!(Some(Format::Zstd), sniff(b"\x28\xb5\x2f\xfd\x00"));
+    assert_eq!(Some(Format::Xz), sniff(b"\xfd7zXZ\x00\x00"));
+    assert_eq!(Some(Format::Bzip2), sniff(b"BZh91AY"));
+    assert_eq!(Some(Format::Zip), sniff(b"PK\x03\x04\x14\x00"));
+    assert_eq!(Some(Format::Png), sniff(b"\x89PNG\r\n\x1a\n\x00"));
+    assert_eq!(Some(Format::Jpeg), sniff(b"\xff\xd8\xff\xe0"));
+    assert_eq!(Some(Format::Gif), sniff(b"GIF89a\x01\x00"));
+    assert_eq!(Some(Format::Pdf), sniff(b"%PDF-1.7\n"));
+    assert_eq!(Some(Format::Elf), sniff(b"\x7fELF\x02\x01"));
+    assert_eq!(Some(Format::Wasm), sniff(b"\x00asm\x01\x00\x00\x00"));
+}
+
+#[test]
+fn sniff_tar() {
+    let mut test = vec![0u8; 512];
+    test[..8].copy_from_slice(b"file.txt");
+    test[257..263].copy_from_slice(b"ustar\x00");
+    assert_eq!(Some(Format::Tar), sniff(&test));
+
+    // Too short for the tar header
+    assert_eq!(None, sniff(&test[..200]));
+}
+
+#[test]
+fn sniff_text() {
+    assert_eq!(Some(Format::Json), sniff(b" \n{\"a\": 1}"));
+    assert_eq!(Some(Format::Json), sniff(b"[1, 2]"));
+    assert_eq!(Some(Format::Xml), sniff(b"<?xml version=\"1.0\"?>"));
+    assert_eq!(
+        Some(Format::Xml),
+        sniff(b"\xef\xbb\xbf<?xml version=\"1.0\"?>")
+    );
+    assert_eq!(Some(Format::Utf8), sniff(b"\xef\xbb\xbfhello"));
+    assert_eq!(Some(Format::Utf16Le), sniff(b"\xff\xfeh\x00"));
+    assert_eq!(Some(Format::Utf16Be), sniff(b"\xfe\xff\x00h"));
+    assert_eq!(None, sniff(b"hello world"));
+    assert_eq!(None, sniff(b""));
+}
+
+#[test]
+fn sniff_lookalikes() {
+    assert_eq!(Some(Format::Json), sniff(b"[ ]"));
+    assert_eq!(Some(Format::Json), sniff(b"[\n  {\"a\": 1}]"));
+    // Text, which only starts like bzip2 or JSON
+    assert_eq!(None, sniff(b"BZhello"));
+    assert_eq!(None, sniff(b"[section]\nkey=1"));
+    assert_eq!(Some(Format::Json), sniff(b"[null]"));
+    assert_eq!(None, sniff(b"[network]\nkey=1"));
+    assert_eq!(None, sniff(b"["));
+    assert_eq!(Some(Format::Json), sniff(b"{ }"));
+    // RTF and other text, which only starts with `{"`
+    assert_eq!(None, sniff(b"{\\rtf1\\ansi hello}"));
+    assert_eq!(None, sniff(b"{ x = 1 }"));
+    assert_eq!(None, sniff(b"{\"a\" = 1}"));
+    assert_eq!(None, sniff(b"{\"a"));
+    assert_eq!(Some(Format::Json), sniff(b"{\"a\\\"b\" : 1}"));
+}
+
+#[test]
+fn sniff_doesnt_wait() {
+    /// Returns `{"a":1}`, then blocks (like a keep-alive socket)
+    struct KeepAlive<'a> {
+        read: &'a [u8],
+    }
+
+    impl Read for KeepAlive<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.read.is_empty() {
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+
+            self.read.read(buf)
+        }
+    }
+
+    let mut read = PeekRead::new(KeepAlive { read: b"{\"a\":1}" });
+    let result = read.sniff();
+    assert!(result.is_ok());
+    assert_eq!(Some(Format::Json), result.unwrap());
+
+    // Only an undecided format reads more
+    let mut read = PeekRead::new(KeepAlive { read: b"{\"a" });
+    let result = read.sniff();
+    assert_eq!(std::io::ErrorKind::WouldBlock, result.unwrap_err().kind());
+}
+
+#[test]
+fn sniff_nothing_consumed() {
+    let test = b"\x1f\x8b\x08\x00rest";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.sniff();
+    assert!(result.is_ok());
+    assert_eq!(Some(Format::Gzip), result.unwrap());
+
+    let mut buf = Vec::new();
+    read.read_to_end(&mut buf).unwrap();
+    assert_eq!(test.as_ref(), buf);
+}
+
+#[test]
+fn sniff_custom() {
+    let sniffer = Sniffer::new()
+        .with_signature("pcap", 0, b"\xd4\xc3\xb2\xa1".as_ref())
+        .with_signature("iso9660", 0x8001, b"CD001".as_ref())
+        // Overrides the built-in zip signature
+        .with_signature("jar", 0, b"PK\x03\x04\x14\x00\x08\x08".as_ref());
+
+    let mut read = PeekRead::new(b"\xd4\xc3\xb2\xa1\x02\x00".as_ref());
+    let result = read.sniff_with(&sniffer);
+    assert!(result.is_ok());
+    assert_eq!(Some(Format::Custom("pcap")), result.unwrap());
+
+    let mut test = vec![0u8; 0x8010];
+    test[0x8001..0x8006].copy_from_slice(b"CD001");
+    let mut read = PeekRead::new(test.as_slice());
+    let result = read.sniff_with(&sniffer);
+    assert_eq!(Some(Format::Custom("iso9660")), result.unwrap());
+
+    // Nothing consumed
+    assert_eq!(0, read.position());
+
+    let mut read = PeekRead::new(b"PK\x03\x04\x14\x00\x08\x08".as_ref());
+    let result = read.sniff_with(&sniffer);
+    assert_eq!(Some(Format::Custom("jar")), result.unwrap());
+
+    // Built-in formats are still detected
+    let mut read = PeekRead::new(b"PK\x03\x04\x0a\x00".as_ref());
+    let result = read.sniff_with(&sniffer);
+    assert_eq!(Some(Format::Zip), result.unwrap());
+}
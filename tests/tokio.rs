@@ -0,0 +1,154 @@
+#![cfg(feature = "tokio")]
+
+use peekbufread::AsyncPeekRead;
+use std::io::ErrorKind;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn peek_then_read() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"hello, world").await.unwrap();
+    drop(client);
+
+    let mut read = AsyncPeekRead::new(server);
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.peek_exact(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello", &buf);
+
+    let result = read.peek_slice(12).await;
+    assert_eq!(b"hello, world", result.unwrap());
+
+    let result = read.peek_slice(13).await;
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", buf.as_slice());
+}
+
+#[tokio::test]
+async fn peek_exact_across_writes() {
+    let (mut client, server) = tokio::io::duplex(4);
+    let writer = tokio::spawn(async move {
+        client.write_all(b"hello, world").await.unwrap();
+    });
+
+    let mut read = AsyncPeekRead::new(server);
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+    writer.await.unwrap();
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+}
+
+#[tokio::test]
+async fn peek_exact_cancelled() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"hello").await.unwrap();
+
+    let mut read = AsyncPeekRead::new(server);
+    let mut buf: [u8; 12] = [0; 12];
+    let result = tokio::time::timeout(Duration::from_millis(10), read.peek_exact(&mut buf)).await;
+    assert!(result.is_err());
+
+    client.write_all(b", world").await.unwrap();
+    drop(client);
+
+    let result = read.peek_exact(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+
+    let mut line = String::new();
+    let result = read.read_line(&mut line).await;
+    assert!(result.is_ok());
+    assert_eq!("hello, world", line);
+}
+
+#[tokio::test]
+async fn peek_partial() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"hello").await.unwrap();
+
+    let mut read = AsyncPeekRead::new(server);
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(5, result.unwrap());
+    assert_eq!(b"hello", &buf[..5]);
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.read_exact(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"he", &buf);
+}
+
+#[tokio::test]
+async fn checkpoint_rollback() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"hello, world").await.unwrap();
+    drop(client);
+
+    let mut read = AsyncPeekRead::new(server);
+    let result = read.peek_slice(12).await;
+    assert!(result.is_ok());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        assert_eq!(b"hello, world", read.buffer());
+        read.consume(7);
+        assert_eq!(b"world", read.buffer());
+        Err(())
+    })
+    .ok();
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", buf.as_slice());
+}
+
+#[tokio::test]
+#[cfg(feature = "async-closures")]
+async fn checkpoint_async_cancelled() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"hello").await.unwrap();
+
+    let mut read = AsyncPeekRead::new(server);
+    let result = tokio::time::timeout(
+        Duration::from_millis(10),
+        read.checkpoint_async(async |read| -> std::io::Result<()> {
+            let mut buf: [u8; 12] = [0; 12];
+            read.read_exact(&mut buf).await?;
+            Ok(())
+        }),
+    )
+    .await;
+    assert!(result.is_err());
+
+    client.write_all(b", world").await.unwrap();
+    drop(client);
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", buf.as_slice());
+}
+
+#[tokio::test]
+async fn default_io_is_tokio() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"hello").await.unwrap();
+    drop(client);
+
+    let mut read: AsyncPeekRead<tokio::io::DuplexStream> = AsyncPeekRead::new(server);
+    let result = read.peek_slice(5).await;
+    assert!(result.is_ok());
+    assert_eq!(b"hello", result.unwrap());
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "std")]
+
+use peekbufread::{Checkpoint, Peek, PeekParser, PeekRead, SlicePeekRead};
+use std::io::{BufReader, Cursor, ErrorKind, Read};
+
+/// Parses "hello" followed by the rest, rolls back if "hello" is missing
+fn parse_hello<P: Peek + Checkpoint + Read>(input: &mut P) -> std::io::Result<Vec<u8>> {
+    let peeked = input.peek_slice(5)?;
+    assert_eq!(b"hello", peeked);
+
+    input.checkpoint(|input| -> std::io::Result<Vec<u8>> {
+        let mut buf: [u8; 5] = [0; 5];
+        input.read_exact(&mut buf)?;
+        if &buf != b"hello" {
+            return Err(ErrorKind::InvalidData.into());
+        }
+
+        let mut rest = Vec::new();
+        input.read_to_end(&mut rest)?;
+        Ok(rest)
+    })
+}
+
+/// Consumes "hello, " and fails, so the checkpoint has to be rolled back
+fn parse_fail<P: Checkpoint + Read>(input: &mut P) -> std::io::Result<()> {
+    input.checkpoint(|input| -> std::io::Result<()> {
+        let mut buf: [u8; 7] = [0; 7];
+        input.read_exact(&mut buf)?;
+        Err(ErrorKind::InvalidData.into())
+    })
+}
+
+fn check<P: Peek + Checkpoint + Read>(mut input: P) {
+    let result = parse_fail(&mut input);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    let result = parse_hello(&mut input);
+    assert!(result.is_ok());
+    assert_eq!(b", world", result.unwrap().as_slice());
+}
+
+#[test]
+fn peek_read() {
+    check(PeekRead::new(b"hello, world".as_ref()));
+}
+
+#[test]
+fn slice_peek_read() {
+    check(SlicePeekRead::new(b"hello, world"));
+}
+
+#[test]
+fn cursor() {
+    check(Cursor::new(b"hello, world".to_vec()));
+}
+
+#[test]
+fn buf_reader() {
+    check(BufReader::with_capacity(8, Cursor::new(b"hello, world")));
+}
+
+#[test]
+fn slice_peek() {
+    let mut input = b"hello, world".as_ref();
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = Peek::peek_exact(&mut input, &mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello", &buf);
+
+    let result = input.peek_slice(13);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    assert_eq!(b"hello, world", input);
+}
+
+#[test]
+fn buf_reader_peek_exact() {
+    let mut input = BufReader::with_capacity(4, Cursor::new(b"hello, world"));
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = Peek::peek_exact(&mut input, &mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", &buf);
+
+    let result = input.peek_slice(5);
+    assert_eq!(ErrorKind::InvalidInput, result.unwrap_err().kind());
+
+    let mut buf: [u8; 13] = [0; 13];
+    let result = Peek::peek_exact(&mut input, &mut buf);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    let mut buf = Vec::new();
+    let result = input.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"hello, world", buf.as_slice());
+}
+
+#[test]
+fn dyn_peek_and_mark() {
+    let mut read = PeekRead::new(b"hello, world".as_ref());
+    let input: &mut dyn Peek = &mut read;
+    let result = input.peek_slice(5);
+    assert_eq!(b"hello", result.unwrap());
+
+    let input: &mut dyn Checkpoint = &mut read;
+    let outer = input.mark().unwrap();
+    let inner = input.mark().unwrap();
+    input.release(inner);
+    assert!(input.reset(outer).is_ok());
+
+    let mut buf: [u8; 7] = [0; 7];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mark = Checkpoint::mark(&mut read).unwrap();
+    let result = read.read_exact(&mut buf[..5]);
+    assert!(result.is_ok());
+    assert_eq!(b"world", &buf[..5]);
+    assert!(read.reset(mark).is_ok());
+
+    let result = read.peek_slice(5);
+    assert_eq!(b"world", result.unwrap());
+}
+
+/// Reads a length byte and as many bytes
+struct LengthPrefixed;
+
+impl PeekParser for LengthPrefixed {
+    type Item = Vec<u8>;
+
+    fn parse<R: Read + ?Sized>(&mut self, read: &mut PeekRead<R>) -> std::io::Result<Vec<u8>> {
+        let len = read.peek_u8()? as usize;
+        let frame = read.peek_slice(1 + len)?[1..].to_vec();
+        read.read_exact(&mut vec![0; 1 + len])?;
+        Ok(frame)
+    }
+}
+
+#[test]
+fn peek_parser_sync() {
+    let test = b"\x02hi\x03abc\x05x";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut parser = LengthPrefixed;
+    assert_eq!(b"hi".to_vec(), parser.parse(&mut read).unwrap());
+    assert_eq!(b"abc".to_vec(), parser.parse(&mut read).unwrap());
+
+    let result = parser.parse(&mut read);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "std")]
+
+use peekbufread::PeekRead;
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn peek_and_read_numbers() {
+    let mut test = Vec::new();
+    test.push(0xfe);
+    test.extend_from_slice(&0x1234u16.to_be_bytes());
+    test.extend_from_slice(&(-2i32).to_le_bytes());
+    test.extend_from_slice(&0x0102030405060708u64.to_be_bytes());
+    test.extend_from_slice(&1.5f32.to_le_bytes());
+    test.extend_from_slice(&(-0.25f64).to_be_bytes());
+    let mut read = PeekRead::new(test.as_slice());
+
+    assert_eq!(0xfe, read.peek_u8().unwrap());
+    assert_eq!(-2, read.peek_i8().unwrap());
+    assert_eq!(0xfe, read.read_u8().unwrap());
+
+    assert_eq!(0x3412, read.peek_u16_le().unwrap());
+    assert_eq!(0x1234, read.read_u16_be().unwrap());
+
+    assert_eq!(-2, read.read_i32_le().unwrap());
+    assert_eq!(0x0102030405060708, read.peek_u64_be().unwrap());
+    assert_eq!(0x0102030405060708, read.read_i64_be().unwrap());
+    assert_eq!(1.5, read.read_f32_le().unwrap());
+    assert_eq!(-0.25, read.read_f64_be().unwrap());
+
+    let result = read.peek_u8();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn peek_array() {
+    let test = b"\x89PNG\r\n";
+    let mut read = PeekRead::new(test.as_ref());
+
+    assert_eq!(*b"\x89PNG", read.peek_array::<4>().unwrap());
+    assert_eq!(*b"\x89PNG\r\n", read.read_array::<6>().unwrap());
+
+    let result = read.read_array::<1>();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn read_eof_consumes_nothing() {
+    let test = b"\x01\x02\x03";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.read_u32_le();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    assert_eq!(0x0102, read.read_u16_be().unwrap());
+    assert_eq!(0x03, read.read_u8().unwrap());
+}
+
+#[test]
+fn checkpoint_read_numbers() {
+    let test = b"\x00\x02hi";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.checkpoint(|read| -> std::io::Result<()> {
+        let len = read.read_u16_be()?;
+        assert_eq!(2, len);
+        let mut buf = vec![0; len as usize + 1];
+        read.read_exact(&mut buf)?;
+        Ok(())
+    });
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    assert_eq!(2, read.read_u16_be().unwrap());
+    assert_eq!(*b"hi", read.read_array::<2>().unwrap());
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "std")]
+
+use peekbufread::PeekRead;
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn varint_u64() {
+    let test = b"\x00\x01\xac\x02\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01";
+    let mut read = PeekRead::new(test.as_ref());
+
+    assert_eq!(0, read.read_varint_u64().unwrap());
+    assert_eq!(1, read.read_varint_u64().unwrap());
+    assert_eq!(300, read.peek_varint_u64().unwrap());
+    assert_eq!(300, read.read_varint_u64().unwrap());
+    assert_eq!(u64::MAX, read.read_varint_u64().unwrap());
+
+    let result = read.read_varint_u64();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+}
+
+#[test]
+fn varint_i64() {
+    let test = b"\x00\x01\x02\x03\xfe\xff\xff\xff\xff\xff\xff\xff\xff\x01";
+    let mut read = PeekRead::new(test.as_ref());
+
+    assert_eq!(0, read.read_varint_i64().unwrap());
+    assert_eq!(-1, read.read_varint_i64().unwrap());
+    assert_eq!(1, read.peek_varint_i64().unwrap());
+    assert_eq!(1, read.read_varint_i64().unwrap());
+    assert_eq!(-2, read.read_varint_i64().unwrap());
+    assert_eq!(i64::MAX, read.read_varint_i64().unwrap());
+}
+
+#[test]
+fn varint_malformed() {
+    // Overflows u64
+    let test = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02";
+    let mut read = PeekRead::new(test.as_ref());
+    let result = read.read_varint_u64();
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    // Longer than 10 bytes
+    let test = b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x01";
+    let mut read = PeekRead::new(test.as_ref());
+    let result = read.read_varint_u64();
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    // Nothing consumed
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, buf.as_slice());
+}
+
+#[test]
+fn varint_padded() {
+    // Padded like in WebAssembly and LLVM
+    let test = b"\x80\x80\x80\x80\x00\x81\x00\x80\x80\x80\x80\x80\x80\x80\x80\x80\x00";
+    let mut read = PeekRead::new(test.as_ref());
+
+    assert_eq!(0, read.read_varint_u64().unwrap());
+    assert_eq!(1, read.read_varint_u64().unwrap());
+    assert_eq!(0, read.read_varint_u64().unwrap());
+    assert_eq!(test.len() as u64, read.position());
+}
+
+#[test]
+fn varint_truncated() {
+    let test = b"\xac";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.read_varint_u64();
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    assert_eq!(0xac, read.read_u8().unwrap());
+}
+
+#[test]
+fn read_varint_delimited() {
+    let test = b"\x05hello\x00\x05wor";
+    let mut read = PeekRead::new(test.as_ref());
+
+    assert_eq!(
+        b"hello",
+        read.read_varint_delimited(100).unwrap().as_slice()
+    );
+    assert!(read.read_varint_delimited(100).unwrap().is_empty());
+
+    // Truncated, the stream is left untouched
+    let result = read.read_varint_delimited(100);
+    assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+
+    let result = read.read_varint_delimited(4);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+
+    let mut buf = Vec::new();
+    let result = read.read_to_end(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"\x05wor", buf.as_slice());
+}
+
+#[test]
+fn read_varint_delimited_overflow() {
+    // Length u64::MAX, which overflows with the prefix even without a limit
+    let test = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01hello";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let result = read.read_varint_delimited(usize::MAX);
+    assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "std")]
+
+use peekbufread::PeekRead;
+use std::cell::Cell;
+use std::io::{IoSliceMut, Read};
+
+/// Counts how often the inner reader was called vectored
+struct CountVectored<'a> {
+    read: &'a [u8],
+    vectored: &'a Cell<usize>,
+}
+
+impl Read for CountVectored<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.read.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> std::io::Result<usize> {
+        self.vectored.set(self.vectored.get() + 1);
+        self.read.read_vectored(bufs)
+    }
+}
+
+#[test]
+fn read_vectored() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut header: [u8; 5] = [0; 5];
+    let mut payload: [u8; 7] = [0; 7];
+    let result =
+        read.read_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(b"hello", &header);
+    assert_eq!(b", world", &payload);
+}
+
+#[test]
+fn partial_peek_read_vectored() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 3] = [0; 3];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut header: [u8; 5] = [0; 5];
+    let mut payload: [u8; 7] = [0; 7];
+    let result =
+        read.read_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(b"hello", &header);
+    assert_eq!(b", world", &payload);
+
+    let result = read.read(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+}
+
+#[test]
+fn peek_read_vectored_buffered() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut header: [u8; 5] = [0; 5];
+    let mut payload: [u8; 2] = [0; 2];
+    let result =
+        read.read_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+    assert!(result.is_ok());
+    assert_eq!(7, result.unwrap());
+    assert_eq!(b"hello", &header);
+    assert_eq!(b", ", &payload);
+
+    let mut buf: [u8; 5] = [0; 5];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(b"world", &buf);
+}
+
+#[test]
+fn peek_vectored() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    let mut header: [u8; 5] = [0; 5];
+    let mut payload: [u8; 7] = [0; 7];
+    let result =
+        read.peek_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(b"hello", &header);
+    assert_eq!(b", world", &payload);
+
+    // Everything is buffered now, the inner reader isn't needed anymore
+    let mut header: [u8; 5] = [0; 5];
+    let mut payload: [u8; 7] = [0; 7];
+    let result =
+        read.peek_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(b"hello", &header);
+    assert_eq!(b", world", &payload);
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+}
+
+#[test]
+fn read_vectored_uses_inner_vectored() {
+    let test = b"hello, world";
+    let vectored = Cell::new(0);
+    let mut read = PeekRead::new(CountVectored {
+        read: test.as_ref(),
+        vectored: &vectored,
+    });
+
+    let mut buf: [u8; 1] = [0; 1];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(0, vectored.get());
+
+    let mut header: [u8; 5] = [0; 5];
+    let mut payload: [u8; 7] = [0; 7];
+    let result =
+        read.read_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+    assert!(result.is_ok());
+    assert_eq!(12, result.unwrap());
+    assert_eq!(1, vectored.get());
+
+    let result = read.peek_vectored(&mut [IoSliceMut::new(&mut header)]);
+    assert!(result.is_ok());
+    assert_eq!(0, result.unwrap());
+    assert_eq!(2, vectored.get());
+}
+
+#[test]
+fn checkpoint_read_vectored_err() {
+    let test = b"hello, world";
+    let mut read = PeekRead::new(test.as_ref());
+
+    let mut buf: [u8; 2] = [0; 2];
+    let result = read.peek_exact(&mut buf);
+    assert!(result.is_ok());
+
+    read.checkpoint(|read| -> Result<(), ()> {
+        let mut header: [u8; 5] = [0; 5];
+        let mut payload: [u8; 7] = [0; 7];
+        let result =
+            read.read_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)]);
+        assert!(result.is_ok());
+        assert_eq!(12, result.unwrap());
+        assert_eq!(b"hello", &header);
+        assert_eq!(b", world", &payload);
+
+        Err(())
+    })
+    .ok();
+
+    let mut buf: [u8; 12] = [0; 12];
+    let result = read.read_exact(&mut buf);
+    assert!(result.is_ok());
+    assert_eq!(test, &buf);
+}